@@ -0,0 +1,203 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs,
+    io::ErrorKind,
+};
+
+use common::{error::Context, Error};
+
+/// Expected answers, stored as a TOML file with one table per day and input:
+///
+/// ```toml
+/// [day5."inputs/day5.txt"]
+/// part1 = "CMZ"
+/// part2 = "MCD"
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<(u8, String), [Option<String>; 2]>,
+}
+
+impl Answers {
+    /// Reads the answers at `path`, an absent file meaning no answer recorded yet.
+    pub fn load(path: &str) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).context(format!("Couldn't read answers from {path}")),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(Error::from(error).context(format!("Couldn't open {path}"))),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        fs::write(path, self.to_string()).context(format!("Couldn't write {path}"))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut answers = Self::default();
+        let mut current = None;
+
+        for (index, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if let Some(header) = trimmed.strip_prefix('[') {
+                current = Some(parse_header(line, header).at_line(index + 1, line)?);
+                continue;
+            }
+
+            let (day, input) = current
+                .clone()
+                .ok_or_else(|| Error::at("Answer outside of a table", line, trimmed))
+                .at_line(index + 1, line)?;
+            let (key, value) = parse_key_value(line, trimmed).at_line(index + 1, line)?;
+            answers.set(day, &input, key, value);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u8, input: &str, part: u8) -> Option<&str> {
+        let parts = self.entries.get(&(day, input.to_string()))?;
+        parts[usize::from(part) - 1].as_deref()
+    }
+
+    pub fn set(&mut self, day: u8, input: &str, part: u8, answer: String) {
+        let parts = self.entries.entry((day, input.to_string())).or_default();
+        parts[usize::from(part) - 1] = Some(answer);
+    }
+
+    /// Recorded days and inputs, in day order.
+    pub fn inputs(&self) -> impl Iterator<Item = (u8, &str)> {
+        self.entries
+            .keys()
+            .map(|(day, input)| (*day, input.as_str()))
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, ((day, input), parts)) in self.entries.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "[day{day}.{}]", quote(input))?;
+            for (part, answer) in (1..).zip(parts) {
+                if let Some(answer) = answer {
+                    writeln!(f, "part{part} = {}", quote(answer))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_header(line: &str, header: &str) -> Result<(u8, String), Error> {
+    let invalid = || {
+        Error::at(
+            "Expected a table such as [day5.\"input.txt\"]",
+            line,
+            header,
+        )
+    };
+
+    let header = header.strip_suffix(']').ok_or_else(invalid)?;
+    let (day, input) = header.split_once('.').ok_or_else(invalid)?;
+    let day = day
+        .trim()
+        .strip_prefix("day")
+        .and_then(|day| day.parse().ok())
+        .ok_or_else(|| Error::at("Expected a day such as day5", line, day))?;
+    let (input, rest) = unquote(input.trim()).ok_or_else(invalid)?;
+    if !rest.trim().is_empty() {
+        return Err(invalid());
+    }
+
+    Ok((day, input))
+}
+
+fn parse_key_value(line: &str, trimmed: &str) -> Result<(u8, String), Error> {
+    let (key, value) = trimmed
+        .split_once('=')
+        .ok_or_else(|| Error::at("Expected part1 = \"...\"", line, trimmed))?;
+    let key = key.trim();
+    let part = match key {
+        "part1" => 1,
+        "part2" => 2,
+        _ => return Err(Error::at("Unknown key, expected part1 or part2", line, key)),
+    };
+    let value = value.trim();
+    match unquote(value) {
+        Some((answer, rest)) if rest.trim().is_empty() => Ok((part, answer)),
+        _ => Err(Error::at("Expected a quoted answer", line, value)),
+    }
+}
+
+/// TOML basic string, escaping what could break the line.
+fn quote(value: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Reads a TOML basic string at the start of `text`, returning it with what follows.
+fn unquote(text: &str) -> Option<(String, &str)> {
+    let mut chars = text.strip_prefix('"')?.char_indices();
+    let mut value = String::new();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &text[index + 2..])),
+            '\\' => value.push(match chars.next()?.1 {
+                'n' => '\n',
+                't' => '\t',
+                '"' => '"',
+                '\\' => '\\',
+                _ => return None,
+            }),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = r###"[day5."inputs/day5.txt"]
+part1 = "CMZ"
+part2 = "MCD"
+
+[day10."inputs/day10.txt"]
+part2 = "##..\n#..#"
+"###;
+
+    #[test]
+    fn round_trip() {
+        let answers = Answers::parse(FILE).unwrap();
+        assert_eq!(answers.get(5, "inputs/day5.txt", 2), Some("MCD"));
+        assert_eq!(answers.get(10, "inputs/day10.txt", 1), None);
+        assert_eq!(answers.get(10, "inputs/day10.txt", 2), Some("##..\n#..#"));
+        assert_eq!(answers.to_string(), FILE);
+    }
+
+    #[test]
+    fn errors() {
+        let error = Answers::parse("[day5.\"a\"]\npart3 = \"x\"").unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.span(), Some(0..5));
+
+        let error = Answers::parse("part1 = \"x\"").unwrap_err();
+        assert_eq!(error.line(), Some(1));
+    }
+}
@@ -0,0 +1,262 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    hint::black_box,
+    io::ErrorKind,
+    time::{Duration, Instant},
+};
+
+use common::{error::Context, info, solution::read_input, DynSolution, Error};
+
+pub struct Settings {
+    pub iterations: usize,
+    pub warmup: usize,
+}
+
+/// Durations of a stage over every iteration, sorted from the fastest.
+struct Timings(Vec<Duration>);
+
+impl Timings {
+    fn measure(
+        settings: &Settings,
+        mut f: impl FnMut() -> Result<(), Error>,
+    ) -> Result<Self, Error> {
+        for _ in 0..settings.warmup {
+            f()?;
+        }
+        let mut durations = Vec::with_capacity(settings.iterations);
+        for _ in 0..settings.iterations.max(1) {
+            let start = Instant::now();
+            f()?;
+            durations.push(start.elapsed());
+        }
+        durations.sort();
+        Ok(Self(durations))
+    }
+
+    fn min(&self) -> Duration {
+        self.0[0]
+    }
+
+    fn median(&self) -> Duration {
+        self.percentile(50)
+    }
+
+    /// Nearest-rank percentile.
+    fn percentile(&self, percent: usize) -> Duration {
+        let rank = (self.0.len() * percent).div_ceil(100);
+        self.0[rank.max(1) - 1]
+    }
+}
+
+type Key = (u8, String, String);
+
+/// Median duration of every day, stage and input, stored as `day stage nanoseconds input` lines.
+#[derive(Default)]
+struct Baseline(BTreeMap<Key, Duration>);
+
+impl Baseline {
+    fn load(path: &str) -> Result<Self, Error> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => return Err(Error::from(error).context(format!("Couldn't open {path}"))),
+        };
+
+        let mut baseline = Self::default();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, median) = parse_line(line)
+                .at_line(index + 1, line)
+                .context(format!("Couldn't read baseline from {path}"))?;
+            baseline.0.insert(key, median);
+        }
+        Ok(baseline)
+    }
+
+    fn save(&self, path: &str) -> Result<(), Error> {
+        let mut text = String::from("# day stage median_ns input\n");
+        for ((day, stage, input), median) in &self.0 {
+            let _ = writeln!(text, "{day} {stage} {} {input}", median.as_nanos());
+        }
+        fs::write(path, text).context(format!("Couldn't write {path}"))
+    }
+}
+
+fn parse_line(line: &str) -> Result<(Key, Duration), Error> {
+    let mut fields = line.splitn(4, ' ');
+    let mut field = |name: &'static str| {
+        fields
+            .next()
+            .filter(|field| !field.is_empty())
+            .ok_or_else(|| Error::at(format!("Missing {name}"), line, &line[line.len()..]))
+    };
+    let (day, stage, nanos, input) = (
+        field("day")?,
+        field("stage")?,
+        field("duration")?,
+        field("input")?,
+    );
+
+    let day = day
+        .parse()
+        .map_err(|e| Error::at("Invalid day", line, day).with_source(e))?;
+    let nanos = nanos
+        .parse()
+        .map_err(|e| Error::at("Invalid duration", line, nanos).with_source(e))?;
+    Ok((
+        (day, stage.to_string(), input.to_string()),
+        Duration::from_nanos(nanos),
+    ))
+}
+
+/// Runs one stage, dropping its result.
+type Stage<'a> = Box<dyn Fn() -> Result<(), Error> + 'a>;
+
+struct Row {
+    key: Key,
+    timings: Timings,
+}
+
+/// Times parsing and both parts of every `(solution, input path)` separately, the input being
+/// read once beforehand, and prints them next to the medians saved at `baseline_path`. With
+/// `save`, the new medians replace the saved ones. A day failing is reported, and the others are
+/// still benchmarked.
+pub fn bench(
+    runs: &[(&dyn DynSolution, String)],
+    settings: &Settings,
+    baseline_path: &str,
+    save: bool,
+) -> Result<(), Error> {
+    let mut baseline = Baseline::load(baseline_path)?;
+    let mut rows = Vec::new();
+    let mut failed = false;
+
+    for (solution, path) in runs {
+        match bench_day(*solution, path, settings) {
+            Ok(day_rows) => rows.extend(day_rows),
+            Err(error) => {
+                eprint!("{}", error.diagnostic());
+                failed = true;
+            }
+        }
+    }
+
+    print_table(&rows, &baseline);
+
+    if save {
+        for row in rows {
+            baseline.0.insert(row.key, row.timings.median());
+        }
+        baseline.save(baseline_path)?;
+        info!("Saved the medians to {baseline_path}");
+    }
+
+    if failed {
+        Err("Some days failed.".into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Rows of the three stages of `solution` on the input at `path`.
+fn bench_day(
+    solution: &dyn DynSolution,
+    path: &str,
+    settings: &Settings,
+) -> Result<Vec<Row>, Error> {
+    let day = solution.day();
+    let input = read_input(Some(path)).context(format!("Day {day}"))?;
+    let parsed = solution.parse(&input).context(format!("Day {day}"))?;
+    solution.describe(parsed.as_ref());
+    let key = |stage: &str| (day, stage.to_string(), path.to_string());
+
+    // Results go through `black_box` so that dropping them can't let a stage be optimised out
+    let stages: [(&str, Stage); 3] = [
+        (
+            "parse",
+            Box::new(|| black_box(solution.parse(&input)).map(drop)),
+        ),
+        (
+            "part1",
+            Box::new(|| black_box(solution.part1(parsed.as_ref())).map(drop)),
+        ),
+        (
+            "part2",
+            Box::new(|| black_box(solution.part2(parsed.as_ref())).map(drop)),
+        ),
+    ];
+    stages
+        .into_iter()
+        .map(|(stage, f)| {
+            let timings = Timings::measure(settings, f).context(format!("Day {day} {stage}"))?;
+            Ok(Row {
+                key: key(stage),
+                timings,
+            })
+        })
+        .collect()
+}
+
+fn print_table(rows: &[Row], baseline: &Baseline) {
+    println!(
+        "Day  Stage  {:>10}  {:>10}  {:>10}  {:>10}  Change",
+        "Min", "Median", "p95", "Baseline"
+    );
+    for Row { key, timings } in rows {
+        let (day, stage, _) = key;
+        let median = timings.median();
+        let (saved, change) = match baseline.0.get(key) {
+            Some(saved) => (
+                format!("{saved:.1?}"),
+                format!("{:+.1}%", relative_change(*saved, median)),
+            ),
+            None => ("-".to_string(), String::new()),
+        };
+        let line = format!(
+            "{day:>3}  {stage:5}  {:>10}  {:>10}  {:>10}  {saved:>10}  {change}",
+            format!("{:.1?}", timings.min()),
+            format!("{median:.1?}"),
+            format!("{:.1?}", timings.percentile(95)),
+        );
+        println!("{}", line.trim_end());
+    }
+}
+
+fn relative_change(before: Duration, after: Duration) -> f64 {
+    (after.as_secs_f64() / before.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0) * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles() {
+        let timings = Timings((1..=20).map(Duration::from_millis).collect());
+        assert_eq!(timings.min(), Duration::from_millis(1));
+        assert_eq!(timings.median(), Duration::from_millis(10));
+        assert_eq!(timings.percentile(95), Duration::from_millis(19));
+
+        let single = Timings(vec![Duration::from_millis(3)]);
+        assert_eq!(single.percentile(95), Duration::from_millis(3));
+    }
+
+    #[test]
+    fn baseline_line() {
+        let (key, median) = parse_line("7 part1 1500 inputs/my day7.txt").unwrap();
+        assert_eq!(
+            key,
+            (7, "part1".to_string(), "inputs/my day7.txt".to_string())
+        );
+        assert_eq!(median, Duration::from_nanos(1500));
+        assert_eq!(
+            parse_line("7 part1 fast x").unwrap_err().span(),
+            Some(8..12)
+        );
+        assert!(parse_line("7 part1 1500").is_err());
+    }
+}
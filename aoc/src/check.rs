@@ -0,0 +1,147 @@
+use std::fmt::{self, Display};
+
+use common::{info, solution::read_input, DynSolution, Error};
+
+use crate::answers::Answers;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    /// Same answer as recorded.
+    Pass,
+    /// The solution returned an error.
+    Fail,
+    /// Another answer than the recorded one.
+    Changed,
+    /// Nothing recorded yet for this part.
+    New,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Changed => "changed",
+            Status::New => "new",
+        };
+        // Forwarding to `str` keeps the width given by the table
+        name.fmt(f)
+    }
+}
+
+struct Row {
+    day: u8,
+    part: u8,
+    input: String,
+    status: Status,
+    details: String,
+}
+
+/// Solves every `(solution, input path)` and prints how the answers compare with the ones
+/// recorded at `answers_path`. With `bless`, new and changed answers are recorded.
+pub fn check(
+    runs: &[(&dyn DynSolution, String)],
+    answers_path: &str,
+    bless: bool,
+) -> Result<(), Error> {
+    let mut answers = Answers::load(answers_path)?;
+    let mut rows = Vec::new();
+
+    for (solution, input) in runs {
+        let day = solution.day();
+        for (part, outcome) in (1..).zip(solve_parts(*solution, input)) {
+            let answer = match outcome {
+                Ok(answer) => answer,
+                Err(error) => {
+                    rows.push(Row {
+                        day,
+                        part,
+                        input: input.clone(),
+                        status: Status::Fail,
+                        details: error.to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            let (status, details) = match answers.get(day, input, part) {
+                None => (Status::New, escape(&answer)),
+                Some(expected) if expected == answer => (Status::Pass, escape(&answer)),
+                Some(expected) => (
+                    Status::Changed,
+                    format!("{}, expected {}", escape(&answer), escape(expected)),
+                ),
+            };
+            if bless && status != Status::Pass {
+                answers.set(day, input, part, answer);
+            }
+            rows.push(Row {
+                day,
+                part,
+                input: input.clone(),
+                status,
+                details,
+            });
+        }
+    }
+
+    print_table(&rows);
+    if bless {
+        answers.save(answers_path)?;
+        info!("Recorded answers in {answers_path}");
+    }
+
+    let count = |status| rows.iter().filter(|row| row.status == status).count();
+    let failed = count(Status::Fail);
+    let changed = if bless { 0 } else { count(Status::Changed) };
+    if failed + changed > 0 {
+        Err(format!("{failed} failed and {changed} changed answers.").into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Both answers, a failure to read or parse the input failing both parts.
+fn solve_parts(solution: &dyn DynSolution, path: &str) -> [Result<String, Error>; 2] {
+    let parsed = read_input(Some(path)).and_then(|input| solution.parse(&input));
+    match parsed {
+        Ok(parsed) => [
+            solution
+                .part1(parsed.as_ref())
+                .map(|answer| answer.to_string()),
+            solution
+                .part2(parsed.as_ref())
+                .map(|answer| answer.to_string()),
+        ],
+        Err(error) => {
+            let message = error.to_string();
+            [Err(error), Err(message.into())]
+        }
+    }
+}
+
+/// Keeps multi-line answers on a single row.
+fn escape(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("{answer:?}")
+    } else {
+        answer.to_string()
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    let width = rows
+        .iter()
+        .map(|row| row.input.len())
+        .max()
+        .unwrap_or(0)
+        .max("Input".len());
+
+    println!("Day  Part  {:width$}  Status   Answer", "Input");
+    for row in rows {
+        println!(
+            "{:>3}  {:>4}  {:width$}  {:7}  {}",
+            row.day, row.part, row.input, row.status, row.details
+        );
+    }
+}
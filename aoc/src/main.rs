@@ -0,0 +1,332 @@
+mod answers;
+mod bench;
+mod check;
+mod play;
+
+use std::{env, process::ExitCode};
+
+use common::{
+    log::{self, Level},
+    report,
+    rng::Rng,
+    solution::{print_answers, read_input},
+    DynSolution, Error,
+};
+
+const USAGE: &str = "Usage:
+    aoc run <day|all> [input|-] [--format text|json|ndjson]
+    aoc run <day|all> [input|-] --check [--bless] [--answers <file>]
+    aoc bench <day|all> [input|-] [--iterations <n>] [--warmup <n>] [--baseline <file>] [--save]
+    aoc play <day> [input|-] [--part 1|2] [--fps <n>] [--every <n>] [--gif <file>] [--scale <n>]
+    aoc gen <day> [--size <n>] [--seed <n>]
+
+Diagnostics go to stderr: -q keeps only warnings, -v adds debug output and -vv traces every step.";
+
+/// Folder where `aoc run all` looks for `dayN.txt` inputs when none is given.
+const INPUTS_DIR: &str = "inputs";
+
+/// Expected answers compared against by `--check`.
+const ANSWERS_FILE: &str = "answers.toml";
+
+/// Median timings compared against by `bench`.
+const BASELINE_FILE: &str = "bench_baseline.txt";
+
+const SOLUTIONS: [&dyn DynSolution; 10] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+];
+
+fn main() -> ExitCode {
+    match run_command(env::args().skip(1)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprint!("{}", error.diagnostic());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_command(args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let options = Options::parse(args)?;
+    log::set_max_level(Level::from_verbosity(options.verbosity));
+    let mut args = options.positional.iter().map(String::as_str);
+
+    match args.next() {
+        Some("run") => {
+            let day = args.next().ok_or(USAGE)?;
+            let input = args.next();
+            if args.next().is_some() {
+                return Err(USAGE.into());
+            }
+            match (day, options.check) {
+                ("all", false) if options.format != Format::Text => {
+                    print_records(&default_runs(input.unwrap_or(INPUTS_DIR)), options.format)
+                }
+                (day, false) if options.format != Format::Text => {
+                    let runs = [(find_solution(day)?, input.unwrap_or("-").to_string())];
+                    print_records(&runs, options.format)
+                }
+                ("all", false) => run_all(input.unwrap_or(INPUTS_DIR)),
+                (day, false) => run(find_solution(day)?, input),
+                ("all", true) => {
+                    let runs = all_runs(input.unwrap_or(INPUTS_DIR), &options.answers)?;
+                    check::check(&runs, &options.answers, options.bless)
+                }
+                (day, true) => {
+                    let input = input
+                        .filter(|&input| input != "-")
+                        .ok_or("--check needs an input file to record answers against.")?;
+                    let runs = [(find_solution(day)?, input.to_string())];
+                    check::check(&runs, &options.answers, options.bless)
+                }
+            }
+        }
+        Some("bench") => {
+            let day = args.next().ok_or(USAGE)?;
+            let input = args.next();
+            if args.next().is_some() {
+                return Err(USAGE.into());
+            }
+            let runs = if day == "all" {
+                default_runs(input.unwrap_or(INPUTS_DIR))
+            } else {
+                vec![(find_solution(day)?, input.unwrap_or("-").to_string())]
+            };
+            bench::bench(&runs, &options.bench, &options.baseline, options.save)
+        }
+        Some("play") => {
+            let day = args.next().ok_or(USAGE)?;
+            let input = args.next().unwrap_or("-");
+            if args.next().is_some() {
+                return Err(USAGE.into());
+            }
+            play::play(find_solution(day)?, input, &options.play)
+        }
+        Some("gen") => {
+            let day = args.next().ok_or(USAGE)?;
+            if args.next().is_some() {
+                return Err(USAGE.into());
+            }
+            let input = find_solution(day)?.generate(options.size, &mut Rng::new(options.seed))?;
+            print!("{input}");
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    /// A single array of records.
+    Json,
+    /// One record per line, printed as soon as the day is solved.
+    Ndjson,
+}
+
+struct Options {
+    positional: Vec<String>,
+    format: Format,
+    check: bool,
+    bless: bool,
+    answers: String,
+    bench: bench::Settings,
+    baseline: String,
+    save: bool,
+    play: play::Settings,
+    /// Rough amount of items in a generated input.
+    size: usize,
+    seed: u64,
+    /// Number of `-v` minus number of `-q`.
+    verbosity: i8,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, Error> {
+        let mut options = Options {
+            positional: Vec::new(),
+            format: Format::Text,
+            check: false,
+            bless: false,
+            answers: ANSWERS_FILE.to_string(),
+            bench: bench::Settings {
+                iterations: 100,
+                warmup: 10,
+            },
+            baseline: BASELINE_FILE.to_string(),
+            save: false,
+            play: play::Settings {
+                part: 2,
+                fps: 10,
+                every: 1,
+                gif: None,
+                scale: 1,
+            },
+            size: 100,
+            seed: 0,
+            verbosity: 0,
+        };
+        let count = |args: &mut dyn Iterator<Item = String>, what: &str| -> Result<usize, Error> {
+            let value = args.next().ok_or(USAGE)?;
+            value
+                .parse()
+                .map_err(|_| format!("Expected a number of {what}, got {value:?}.").into())
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => {
+                    options.format = match args.next().as_deref() {
+                        Some("text") => Format::Text,
+                        Some("json") => Format::Json,
+                        Some("ndjson") => Format::Ndjson,
+                        _ => return Err("--format should be text, json or ndjson.".into()),
+                    }
+                }
+                "--check" => options.check = true,
+                "--bless" => options.bless = true,
+                "--answers" => options.answers = args.next().ok_or(USAGE)?,
+                "--iterations" => options.bench.iterations = count(&mut args, "iterations")?,
+                "--warmup" => options.bench.warmup = count(&mut args, "warmup iterations")?,
+                "--baseline" => options.baseline = args.next().ok_or(USAGE)?,
+                "--save" => options.save = true,
+                "--part" => {
+                    options.play.part = match args.next().as_deref() {
+                        Some("1") => 1,
+                        Some("2") => 2,
+                        _ => return Err("--part should be 1 or 2.".into()),
+                    }
+                }
+                "--fps" => {
+                    options.play.fps = u32::try_from(count(&mut args, "frames per second")?)
+                        .map_err(|_| "--fps is too large.")?
+                }
+                "--every" => options.play.every = count(&mut args, "frames")?,
+                "--gif" => options.play.gif = Some(args.next().ok_or(USAGE)?),
+                "--scale" => {
+                    options.play.scale = i32::try_from(count(&mut args, "pixels")?)
+                        .ok()
+                        .filter(|&scale| scale > 0)
+                        .ok_or("--scale should be between 1 and 2147483647.")?
+                }
+                "--size" => options.size = count(&mut args, "items")?,
+                "--seed" => {
+                    let seed = args.next().ok_or(USAGE)?;
+                    options.seed = seed
+                        .parse()
+                        .map_err(|_| format!("Expected a numeric seed, got {seed:?}."))?;
+                }
+                "-v" | "--verbose" => options.verbosity = options.verbosity.saturating_add(1),
+                "-vv" => options.verbosity = options.verbosity.saturating_add(2),
+                "-q" | "--quiet" => options.verbosity = options.verbosity.saturating_sub(1),
+                // A lone `-` is stdin, a positional argument
+                flag if flag.starts_with('-') && flag != "-" => {
+                    return Err(format!("Unknown flag {flag}.").into())
+                }
+                _ => options.positional.push(arg),
+            }
+        }
+
+        if options.check && options.format != Format::Text {
+            return Err("--check only prints a table, without --format.".into());
+        }
+        if options.bless && !options.check {
+            return Err("--bless only makes sense along with --check.".into());
+        }
+        Ok(options)
+    }
+}
+
+fn find_solution(day: &str) -> Result<&'static dyn DynSolution, Error> {
+    let day: u8 = day.parse().or(Err("Day should be a number or \"all\"."))?;
+    solution_for(day)
+}
+
+fn solution_for(day: u8) -> Result<&'static dyn DynSolution, Error> {
+    SOLUTIONS
+        .into_iter()
+        .find(|solution| solution.day() == day)
+        .ok_or_else(|| format!("No solution for day {day}.").into())
+}
+
+/// Every day on its `dayN.txt` input.
+fn default_runs(inputs_dir: &str) -> Vec<(&'static dyn DynSolution, String)> {
+    SOLUTIONS
+        .into_iter()
+        .map(|solution| (solution, format!("{inputs_dir}/day{}.txt", solution.day())))
+        .collect()
+}
+
+/// Every day on its default input, along with any other input having recorded answers.
+fn all_runs(
+    inputs_dir: &str,
+    answers_path: &str,
+) -> Result<Vec<(&'static dyn DynSolution, String)>, Error> {
+    let mut runs = default_runs(inputs_dir);
+
+    for (day, input) in answers::Answers::load(answers_path)?.inputs() {
+        if !runs
+            .iter()
+            .any(|(solution, path)| solution.day() == day && path == input)
+        {
+            runs.push((solution_for(day)?, input.to_string()));
+        }
+    }
+    runs.sort_by_key(|(solution, _)| solution.day());
+    Ok(runs)
+}
+
+fn run(solution: &dyn DynSolution, path: Option<&str>) -> Result<(), Error> {
+    let input = read_input(path)?;
+    print_answers(solution, &input)
+}
+
+fn run_all(inputs_dir: &str) -> Result<(), Error> {
+    let mut failed = false;
+
+    for solution in SOLUTIONS {
+        let path = format!("{inputs_dir}/day{}.txt", solution.day());
+        println!("Day {}", solution.day());
+        if let Err(error) = run(solution, Some(&path)) {
+            eprint!("{}", error.diagnostic());
+            failed = true;
+        }
+    }
+
+    if failed {
+        Err("Some days failed.".into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Prints the outcome of every part as JSON, failing when any of them did.
+fn print_records(runs: &[(&dyn DynSolution, String)], format: Format) -> Result<(), Error> {
+    let mut records = Vec::new();
+    for (solution, path) in runs {
+        let day_records = report::solve(*solution, path, read_input(Some(path)));
+        if format == Format::Ndjson {
+            for record in &day_records {
+                println!("{}", record.to_json());
+            }
+        }
+        records.extend(day_records);
+    }
+    if format == Format::Json {
+        println!("{}", report::to_json(&records));
+    }
+
+    if records.iter().any(|record| record.answer.is_err()) {
+        Err("Some parts failed.".into())
+    } else {
+        Ok(())
+    }
+}
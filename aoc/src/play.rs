@@ -0,0 +1,27 @@
+use common::{frames::Recorder, info, solution::read_input, DynSolution, Error};
+
+pub struct Settings {
+    pub part: u8,
+    pub fps: u32,
+    /// Keeps one frame out of every `every`, for the longer simulations.
+    pub every: usize,
+    /// GIF file to write instead of playing back in the terminal.
+    pub gif: Option<String>,
+    pub scale: i32,
+}
+
+/// Replays a part of `solution` on the input at `path`, frame by frame.
+pub fn play(solution: &dyn DynSolution, path: &str, settings: &Settings) -> Result<(), Error> {
+    let input = solution.parse(&read_input(Some(path))?)?;
+    let mut recorder = Recorder::new(settings.every);
+    solution.record(input.as_ref(), settings.part, &mut recorder)?;
+
+    match &settings.gif {
+        Some(gif) => {
+            recorder.save_gif(gif, settings.fps, settings.scale)?;
+            info!("Wrote {} frames to {gif}", recorder.frames().len());
+            Ok(())
+        }
+        None => recorder.play(settings.fps),
+    }
+}
@@ -0,0 +1,23 @@
+//! Checks every example in `fixtures/`, see `common::fixtures`.
+
+use std::{env, path::Path, process::ExitCode};
+
+use common::{fixtures, DynSolution};
+
+fn main() -> ExitCode {
+    let solutions: [&dyn DynSolution; 10] = [
+        &day1::Day1,
+        &day2::Day2,
+        &day3::Day3,
+        &day4::Day4,
+        &day5::Day5,
+        &day6::Day6,
+        &day7::Day7,
+        &day8::Day8,
+        &day9::Day9,
+        &day10::Day10,
+    ];
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../fixtures");
+    let args: Vec<String> = env::args().skip(1).collect();
+    fixtures::run(&root, &solutions, &args)
+}
@@ -0,0 +1,88 @@
+use std::fmt::{self, Display};
+
+use crate::grid::Grid;
+
+/// Answer of a part, keeping its kind for machine-readable output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Picture made of text rows, such as a CRT screen.
+    Rows(Vec<String>),
+}
+
+impl Answer {
+    /// Name of the kind of answer, as written in reports.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) => "integer",
+            Answer::Text(_) => "text",
+            Answer::Rows(_) => "rows",
+        }
+    }
+}
+
+/// Rows are written one per line.
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{value}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Rows(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! integer_answer {
+    ($($type:ty),*) => {
+        $(
+            impl From<$type> for Answer {
+                fn from(value: $type) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+integer_answer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl<T: Display> From<Grid<T>> for Answer {
+    fn from(grid: Grid<T>) -> Self {
+        Answer::Rows(
+            grid.rows()
+                .map(|row| row.iter().map(ToString::to_string).collect())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::Pair;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Answer::from(42usize), Answer::Integer(42));
+        assert_eq!(Answer::from(-3i32).to_string(), "-3");
+        assert_eq!(Answer::from("CMZ").kind(), "text");
+
+        let grid = Grid::from_fn(Pair { x: 3, y: 2 }, |p| if p.x == p.y { '#' } else { '.' });
+        let answer = Answer::from(grid.clone());
+        assert_eq!(answer, Answer::Rows(vec!["#..".into(), ".#.".into()]));
+        assert_eq!(answer.to_string(), grid.to_string());
+    }
+}
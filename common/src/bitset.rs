@@ -0,0 +1,371 @@
+//! Sets of small integers stored as bits: [`FixedBitSet`] holds `64 * WORDS` of them without
+//! allocating, [`BitSet`] grows as needed.
+
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
+
+const WORD: usize = u64::BITS as usize;
+
+/// Word holding `bit`, and the mask of that bit within it.
+fn locate(bit: usize) -> (usize, u64) {
+    (bit / WORD, 1 << (bit % WORD))
+}
+
+/// Set bits of `words`, in increasing order.
+#[derive(Debug, Clone)]
+pub struct Bits<'a> {
+    words: &'a [u64],
+    /// Index of the word after `current`.
+    next: usize,
+    current: u64,
+}
+
+impl<'a> Bits<'a> {
+    fn new(words: &'a [u64]) -> Self {
+        Self {
+            words,
+            next: 0,
+            current: 0,
+        }
+    }
+}
+
+impl Iterator for Bits<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.current = *self.words.get(self.next)?;
+            self.next += 1;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        Some((self.next - 1) * WORD + bit)
+    }
+}
+
+/// Set of the integers below `64 * WORDS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedBitSet<const WORDS: usize> {
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> FixedBitSet<WORDS> {
+    pub const CAPACITY: usize = WORDS * WORD;
+
+    pub fn new() -> Self {
+        Self { words: [0; WORDS] }
+    }
+
+    /// Adds `bit`, returning whether it was missing.
+    ///
+    /// # Panics
+    ///
+    /// When `bit` isn't below [`Self::CAPACITY`].
+    pub fn insert(&mut self, bit: usize) -> bool {
+        assert!(
+            bit < Self::CAPACITY,
+            "{bit} is out of a {}-bit set",
+            Self::CAPACITY
+        );
+        let (word, mask) = locate(bit);
+        let missing = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        missing
+    }
+
+    /// Takes `bit` out, returning whether it was there.
+    pub fn remove(&mut self, bit: usize) -> bool {
+        let (word, mask) = locate(bit);
+        match self.words.get_mut(word) {
+            Some(word) => {
+                let present = *word & mask != 0;
+                *word &= !mask;
+                present
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(&self, bit: usize) -> bool {
+        let (word, mask) = locate(bit);
+        self.words.get(word).is_some_and(|word| word & mask != 0)
+    }
+
+    /// Number of bits set.
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .all(|(a, b)| a & !b == 0)
+    }
+
+    pub fn iter(&self) -> Bits<'_> {
+        Bits::new(&self.words)
+    }
+
+    fn combine(self, other: Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        Self {
+            words: std::array::from_fn(|index| f(self.words[index], other.words[index])),
+        }
+    }
+}
+
+impl<const WORDS: usize> Default for FixedBitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> BitAnd for FixedBitSet<WORDS> {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.combine(other, |a, b| a & b)
+    }
+}
+
+impl<const WORDS: usize> BitOr for FixedBitSet<WORDS> {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.combine(other, |a, b| a | b)
+    }
+}
+
+impl<const WORDS: usize> BitXor for FixedBitSet<WORDS> {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self {
+        self.combine(other, |a, b| a ^ b)
+    }
+}
+
+/// Bits of the first set missing from the second one.
+impl<const WORDS: usize> Sub for FixedBitSet<WORDS> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.combine(other, |a, b| a & !b)
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for FixedBitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(bits: I) -> Self {
+        let mut set = Self::new();
+        set.extend(bits);
+        set
+    }
+}
+
+impl<const WORDS: usize> Extend<usize> for FixedBitSet<WORDS> {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, bits: I) {
+        for bit in bits {
+            self.insert(bit);
+        }
+    }
+}
+
+impl<'a, const WORDS: usize> IntoIterator for &'a FixedBitSet<WORDS> {
+    type Item = usize;
+    type IntoIter = Bits<'a>;
+
+    fn into_iter(self) -> Bits<'a> {
+        self.iter()
+    }
+}
+
+/// Set of integers, growing to fit the largest one.
+#[derive(Debug, Clone, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `bit`, returning whether it was missing.
+    pub fn insert(&mut self, bit: usize) -> bool {
+        let (word, mask) = locate(bit);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let missing = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        missing
+    }
+
+    /// Takes `bit` out, returning whether it was there.
+    pub fn remove(&mut self, bit: usize) -> bool {
+        let (word, mask) = locate(bit);
+        match self.words.get_mut(word) {
+            Some(word) => {
+                let present = *word & mask != 0;
+                *word &= !mask;
+                present
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(&self, bit: usize) -> bool {
+        let (word, mask) = locate(bit);
+        self.words.get(word).is_some_and(|word| word & mask != 0)
+    }
+
+    /// Number of bits set.
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        (self - other).is_empty()
+    }
+
+    pub fn iter(&self) -> Bits<'_> {
+        Bits::new(&self.words)
+    }
+
+    /// Words of both sets combined by `f`, the shorter one being padded with zeros.
+    fn combine(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        let len = self.words.len().max(other.words.len());
+        let word = |words: &[u64], index| words.get(index).copied().unwrap_or(0);
+        Self {
+            words: (0..len)
+                .map(|index| f(word(&self.words, index), word(&other.words, index)))
+                .collect(),
+        }
+    }
+}
+
+/// Sets are equal when they hold the same bits, however many words they use.
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        (self ^ other).is_empty()
+    }
+}
+
+impl Eq for BitSet {}
+
+impl BitAnd for &BitSet {
+    type Output = BitSet;
+
+    fn bitand(self, other: Self) -> BitSet {
+        self.combine(other, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitSet {
+    type Output = BitSet;
+
+    fn bitor(self, other: Self) -> BitSet {
+        self.combine(other, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BitSet {
+    type Output = BitSet;
+
+    fn bitxor(self, other: Self) -> BitSet {
+        self.combine(other, |a, b| a ^ b)
+    }
+}
+
+/// Bits of the first set missing from the second one.
+impl Sub for &BitSet {
+    type Output = BitSet;
+
+    fn sub(self, other: Self) -> BitSet {
+        self.combine(other, |a, b| a & !b)
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(bits: I) -> Self {
+        let mut set = Self::new();
+        set.extend(bits);
+        set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, bits: I) {
+        for bit in bits {
+            self.insert(bit);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a BitSet {
+    type Item = usize;
+    type IntoIter = Bits<'a>;
+
+    fn into_iter(self) -> Bits<'a> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed() {
+        let mut a: FixedBitSet<2> = [1, 64, 127, 5].into_iter().collect();
+        assert_eq!(a.len(), 4);
+        assert!(a.contains(64) && !a.contains(63) && !a.contains(1000));
+        assert!(!a.insert(5));
+        assert!(a.remove(5) && !a.remove(5));
+        assert_eq!(a.iter().collect::<Vec<_>>(), [1, 64, 127]);
+
+        let b: FixedBitSet<2> = [1, 2, 127].into_iter().collect();
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), [1, 127]);
+        assert_eq!((a | b).len(), 4);
+        assert_eq!((a ^ b).iter().collect::<Vec<_>>(), [2, 64]);
+        assert_eq!((a - b).iter().collect::<Vec<_>>(), [64]);
+        assert!((a & b).is_subset(&a) && !a.is_subset(&b));
+        assert!(FixedBitSet::<2>::new().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "128 is out of a 128-bit set")]
+    fn fixed_capacity() {
+        FixedBitSet::<2>::new().insert(128);
+    }
+
+    #[test]
+    fn growable() {
+        let a: BitSet = [3, 200, 70].into_iter().collect();
+        let b: BitSet = [3, 4].into_iter().collect();
+        assert_eq!(a.iter().collect::<Vec<_>>(), [3, 70, 200]);
+        assert_eq!((&a & &b).iter().collect::<Vec<_>>(), [3]);
+        assert_eq!((&a | &b).len(), 4);
+        assert_eq!((&a - &b).iter().collect::<Vec<_>>(), [70, 200]);
+        assert_eq!((&b ^ &a).len(), 3);
+
+        let mut c = b.clone();
+        c.insert(300);
+        c.remove(300);
+        assert_eq!(c, b);
+        assert!(!a.contains(1000) && (&a & &b).is_subset(&b));
+    }
+}
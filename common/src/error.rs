@@ -0,0 +1,233 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+    ops::Range,
+};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+type Source = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// An error in the puzzle input, optionally pointing at the offending text.
+///
+/// Parsers working on a single line locate the error with [`Error::at`], while
+/// the caller iterating over the input attaches the line number with [`Error::at_line`].
+#[derive(Debug)]
+pub struct Error {
+    message: Cow<'static, str>,
+    line: Option<usize>,
+    snippet: Option<String>,
+    span: Option<Range<usize>>,
+    source: Option<Source>,
+}
+
+impl Error {
+    pub fn new(message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+            snippet: None,
+            span: None,
+            source: None,
+        }
+    }
+
+    /// Error about `part`, which must be a subslice of `line`.
+    pub fn at(message: impl Into<Cow<'static, str>>, line: &str, part: &str) -> Self {
+        let start = (part.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|start| start + part.len() <= line.len())
+            .or_else(|| line.find(part))
+            .unwrap_or(0);
+        Self::new(message).with_span(line, start..start + part.len())
+    }
+
+    /// Points the error at the byte range `span` of `line`.
+    pub fn with_span(mut self, line: &str, span: Range<usize>) -> Self {
+        self.snippet = Some(line.to_string());
+        self.span = Some(span);
+        self
+    }
+
+    /// Attaches the 1-based line number, keeping the span if one has already been set.
+    pub fn at_line(mut self, number: usize, line: &str) -> Self {
+        if self.line.is_none() {
+            self.line = Some(number);
+        }
+        if self.snippet.is_none() {
+            self.snippet = Some(line.to_string());
+        }
+        self
+    }
+
+    pub fn with_source(mut self, source: impl Into<Source>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Wraps this error in a higher-level one, keeping its location.
+    pub fn context(self, message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            message: message.into(),
+            line: self.line,
+            snippet: self.snippet.clone(),
+            span: self.span.clone(),
+            source: Some(Box::new(self)),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    pub fn snippet(&self) -> Option<&str> {
+        self.snippet.as_deref()
+    }
+
+    /// Multi-line report with the offending line and a caret under the span.
+    pub fn diagnostic(&self) -> Diagnostic<'_> {
+        Diagnostic(self)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref().map(|source| source as _)
+    }
+}
+
+impl From<&'static str> for Error {
+    fn from(message: &'static str) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::new(error.to_string()).with_source(error)
+    }
+}
+
+/// Adds input context to any error convertible into [`Error`].
+pub trait Context<T> {
+    fn at_line(self, number: usize, line: &str) -> Result<T>;
+    fn context(self, message: impl Into<Cow<'static, str>>) -> Result<T>;
+}
+
+impl<T, E: Into<Error>> Context<T> for Result<T, E> {
+    fn at_line(self, number: usize, line: &str) -> Result<T> {
+        self.map_err(|error| error.into().at_line(number, line))
+    }
+
+    fn context(self, message: impl Into<Cow<'static, str>>) -> Result<T> {
+        self.map_err(|error| error.into().context(message))
+    }
+}
+
+pub struct Diagnostic<'a>(&'a Error);
+
+impl Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let error = self.0;
+        writeln!(f, "error: {}", error.message)?;
+
+        if let Some(snippet) = &error.snippet {
+            let span = error.span.clone().unwrap_or(0..snippet.len());
+            let start = column(snippet, span.start);
+            let width = column(snippet, span.end).saturating_sub(start).max(1);
+            let number = error.line.map(|line| line.to_string()).unwrap_or_default();
+            let gutter = " ".repeat(number.len());
+
+            match error.line {
+                Some(line) => writeln!(f, "{gutter}--> line {line}, column {}", start + 1)?,
+                None => writeln!(f, "{gutter}--> column {}", start + 1)?,
+            }
+            writeln!(f, "{gutter} |")?;
+            writeln!(f, "{number} | {snippet}")?;
+            writeln!(f, "{gutter} | {:start$}{}", "", "^".repeat(width))?;
+        }
+
+        // Wrapped errors such as `io::Error` often repeat their message as their own source.
+        let mut last = error.to_string();
+        let mut source = std::error::Error::source(error);
+        while let Some(cause) = source {
+            let message = cause.to_string();
+            if message != last {
+                writeln!(f, "caused by: {message}")?;
+            }
+            last = message;
+            source = cause.source();
+        }
+        Ok(())
+    }
+}
+
+/// Number of characters before the byte offset `index`.
+fn column(text: &str, index: usize) -> usize {
+    text.get(..index)
+        .map_or(text.chars().count(), |prefix| prefix.chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn span_of_subslice() {
+        let line = "move 1 from x to 3";
+        let error = Error::at("Couldn't parse number", line, &line[12..13]);
+        assert_eq!(error.span(), Some(12..13));
+        assert_eq!(error.snippet(), Some(line));
+    }
+
+    #[test]
+    fn line_keeps_span() {
+        let line = "addx 1";
+        let error = Error::at("Bad", line, &line[5..]).at_line(3, line);
+        assert_eq!(error.line(), Some(3));
+        assert_eq!(error.span(), Some(5..6));
+        assert_eq!(error.to_string(), "line 3: Bad");
+    }
+
+    #[test]
+    fn diagnostic() {
+        let line = "move 1 from x to 3";
+        let error = Error::at("Couldn't parse number", line, &line[12..13])
+            .at_line(12, line)
+            .context("Couldn't parse movement");
+        assert_eq!(
+            error.diagnostic().to_string(),
+            "\
+error: Couldn't parse movement
+  --> line 12, column 13
+   |
+12 | move 1 from x to 3
+   |             ^
+caused by: line 12: Couldn't parse number
+"
+        );
+    }
+}
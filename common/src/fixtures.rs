@@ -0,0 +1,230 @@
+//! Example inputs checked against their expected answers, one test per fixture file.
+//!
+//! Fixtures live in `fixtures/dayN/<name>.in`, next to `<name>.part1.expected` and
+//! `<name>.part2.expected` holding the answer of each part. A part without expected file isn't
+//! checked, so that an input can be specific to one part.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use crate::{
+    error::{Context, Error},
+    solution::DynSolution,
+};
+
+/// Whether a part gave the expected answer, and if not, what it gave instead.
+pub type Outcome = Result<(), Error>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub day: u8,
+    pub name: String,
+    pub input: PathBuf,
+    /// Expected answer of each part, without trailing whitespace.
+    pub expected: [Option<String>; 2],
+}
+
+impl Fixture {
+    /// Name of the test checking `part`, such as `day5/example/part1`.
+    pub fn test_name(&self, part: usize) -> String {
+        format!("day{}/{}/part{part}", self.day, self.name)
+    }
+
+    /// Parts having an expected answer.
+    pub fn parts(&self) -> impl Iterator<Item = usize> + '_ {
+        (1..)
+            .zip(&self.expected)
+            .filter_map(|(part, expected)| expected.as_ref().map(|_| part))
+    }
+
+    /// Solves the input with `solution`, returning for each checked part whether its answer
+    /// matches, or why not. Failing to read or parse the input fails every part.
+    pub fn check(&self, solution: &dyn DynSolution) -> Result<Vec<(usize, Outcome)>, Error> {
+        let input = fs::read_to_string(&self.input).map_err(|e| {
+            Error::from(e).context(format!("Couldn't read {}", self.input.display()))
+        })?;
+        let input = solution.parse(&input)?;
+
+        let outcomes = self.parts().map(|part| {
+            let answer = match part {
+                1 => solution.part1(input.as_ref()),
+                _ => solution.part2(input.as_ref()),
+            };
+            let expected = self.expected[part - 1].as_deref().unwrap_or_default();
+            let outcome = answer.and_then(|answer| {
+                let answer = answer.to_string();
+                if answer.trim_end() == expected {
+                    Ok(())
+                } else {
+                    Err(format!("Expected:\n{expected}\nGot:\n{answer}").into())
+                }
+            });
+            (part, outcome)
+        });
+        Ok(outcomes.collect())
+    }
+}
+
+/// Every fixture below `root`, sorted by day then name.
+pub fn discover(root: &Path) -> Result<Vec<Fixture>, Error> {
+    let mut fixtures = Vec::new();
+    let read_dir = |path: &Path| {
+        fs::read_dir(path)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| Ok(entry?.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(|e| Error::from(e).context(format!("Couldn't list {}", path.display())))
+    };
+
+    for folder in read_dir(root)? {
+        let Some(day) = file_name(&folder)
+            .strip_prefix("day")
+            .and_then(|day| day.parse().ok())
+        else {
+            continue;
+        };
+        for input in read_dir(&folder)? {
+            if input.extension().is_none_or(|extension| extension != "in") {
+                continue;
+            }
+            let name = input.file_stem().unwrap_or_default().to_string_lossy();
+            let expected = [1, 2].map(|part| {
+                let path = folder.join(format!("{name}.part{part}.expected"));
+                fs::read_to_string(path)
+                    .ok()
+                    .map(|answer| answer.trim_end().to_string())
+            });
+            fixtures.push(Fixture {
+                day,
+                name: name.to_string(),
+                input,
+                expected,
+            });
+        }
+    }
+
+    fixtures.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    Ok(fixtures)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Entry point of a `harness = false` test: checks every fixture below `root` solved by one of
+/// `solutions`, printing one line per part like the standard test harness does. Arguments not
+/// starting with `-` filter the tests by name.
+pub fn run(root: &Path, solutions: &[&dyn DynSolution], args: &[String]) -> ExitCode {
+    let filters: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| !arg.starts_with('-'))
+        .collect();
+    let selected = |name: &str| filters.is_empty() || filters.iter().any(|f| name.contains(f));
+
+    let fixtures = match discover(root).context("Couldn't discover fixtures") {
+        Ok(fixtures) => fixtures,
+        Err(error) => {
+            eprint!("{}", error.diagnostic());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failures = Vec::new();
+    let mut passed = 0;
+    for fixture in &fixtures {
+        let name = format!("day{}/{}", fixture.day, fixture.name);
+        if fixture.parts().next().is_none() && selected(&name) {
+            println!("test {name} ... FAILED");
+            failures.push((name, "No expected answer for either part".to_string()));
+            continue;
+        }
+        let parts: Vec<_> = fixture
+            .parts()
+            .filter(|&part| selected(&fixture.test_name(part)))
+            .collect();
+        if parts.is_empty() {
+            continue;
+        }
+        let outcomes = match solutions.iter().find(|s| s.day() == fixture.day) {
+            Some(solution) => fixture.check(*solution),
+            None => Err(format!("No solution for day {}", fixture.day).into()),
+        };
+        let outcomes: Vec<_> = match outcomes {
+            Ok(outcomes) => outcomes
+                .into_iter()
+                .map(|(part, outcome)| (part, outcome.map_err(|e| e.diagnostic().to_string())))
+                .collect(),
+            Err(error) => {
+                let report = error.diagnostic().to_string();
+                parts
+                    .iter()
+                    .map(|&part| (part, Err(report.clone())))
+                    .collect()
+            }
+        };
+
+        for (part, outcome) in outcomes
+            .into_iter()
+            .filter(|(part, _)| parts.contains(part))
+        {
+            let name = fixture.test_name(part);
+            match outcome {
+                Ok(()) => {
+                    println!("test {name} ... ok");
+                    passed += 1;
+                }
+                Err(report) => {
+                    println!("test {name} ... FAILED");
+                    failures.push((name, report));
+                }
+            }
+        }
+    }
+
+    for (name, report) in &failures {
+        println!("\n---- {name} ----\n{report}");
+    }
+    let status = if failures.is_empty() { "ok" } else { "FAILED" };
+    println!(
+        "\ntest result: {status}. {passed} passed; {} failed",
+        failures.len()
+    );
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovery() {
+        let root = std::env::temp_dir().join(format!("fixtures-{}", std::process::id()));
+        let day = root.join("day3");
+        fs::create_dir_all(&day).unwrap();
+        fs::create_dir_all(root.join("notes")).unwrap();
+        fs::write(day.join("b.in"), "").unwrap();
+        fs::write(day.join("a.in"), "").unwrap();
+        fs::write(day.join("a.part2.expected"), "12\n").unwrap();
+        fs::write(day.join("README"), "").unwrap();
+
+        let fixtures = discover(&root).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        let names: Vec<_> = fixtures.iter().map(|f| f.test_name(2)).collect();
+        assert_eq!(names, ["day3/a/part2", "day3/b/part2"]);
+        assert_eq!(fixtures[0].expected, [None, Some("12".to_string())]);
+    }
+}
@@ -0,0 +1,108 @@
+//! Snapshots of a simulation, played back in the terminal or saved as an animated GIF.
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+use crate::{error::Error, gif, render::Image};
+
+/// One step of a simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub image: Image,
+    /// Shown above the image during playback.
+    pub caption: String,
+}
+
+impl Frame {
+    pub fn new(image: Image, caption: impl Into<String>) -> Self {
+        Self {
+            image,
+            caption: caption.into(),
+        }
+    }
+}
+
+/// Collects the frames pushed by a simulation, keeping one out of every `every` of them.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    every: usize,
+    pushed: usize,
+    frames: Vec<Frame>,
+}
+
+impl Recorder {
+    pub fn new(every: usize) -> Self {
+        Self {
+            every: every.max(1),
+            pushed: 0,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Records the frame made by `frame`, which is only called for the frames being kept.
+    pub fn push(&mut self, frame: impl FnOnce() -> Frame) {
+        if self.pushed.is_multiple_of(self.every) {
+            self.frames.push(frame());
+        }
+        self.pushed += 1;
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Draws every frame in place, `fps` of them per second.
+    pub fn play(&self, fps: u32) -> Result<(), Error> {
+        let delay = Duration::from_secs(1) / fps.max(1);
+        let mut stdout = io::stdout().lock();
+        for frame in &self.frames {
+            // Back to the top left corner, then clears the screen
+            write!(
+                stdout,
+                "\x1b[H\x1b[2J{}\n{}",
+                frame.caption,
+                frame.image.to_ansi()
+            )?;
+            stdout.flush()?;
+            thread::sleep(delay);
+        }
+        Ok(())
+    }
+
+    /// Writes the frames as a looping GIF, `fps` of them per second, every pixel being enlarged
+    /// to a square of `scale` pixels.
+    pub fn save_gif(&self, path: impl AsRef<Path>, fps: u32, scale: i32) -> Result<(), Error> {
+        let path = path.as_ref();
+        let images: Vec<Image> = self.frames.iter().map(|f| f.image.scale(scale)).collect();
+        let delay = (100 / fps.max(1)).max(1) as u16;
+        fs::write(path, gif::encode(&images, delay)?)
+            .map_err(|e| Error::from(e).context(format!("Couldn't write {}", path.display())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grid::Grid, pair::Pair, render::Rgb};
+
+    #[test]
+    fn keeps_one_frame_out_of_every() {
+        let mut recorder = Recorder::new(3);
+        let mut made = 0;
+        for step in 0..7 {
+            recorder.push(|| {
+                made += 1;
+                let image = Grid::filled(Pair { x: 1, y: 1 }, Rgb::BLACK).into();
+                Frame::new(image, format!("step {step}"))
+            });
+        }
+        assert_eq!(made, 3);
+        let captions: Vec<_> = recorder.frames().iter().map(|f| &f.caption[..]).collect();
+        assert_eq!(captions, ["step 0", "step 3", "step 6"]);
+    }
+}
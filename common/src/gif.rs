@@ -0,0 +1,275 @@
+//! Animated GIF encoder, for the frames of a [`Recorder`](crate::frames::Recorder).
+//!
+//! Every frame shares a single palette, so that all of them together can't use more than 256
+//! colours, and they all have to be the same size.
+
+use std::collections::{hash_map::Entry, HashMap};
+
+use crate::{
+    error::Error,
+    pair::Pair,
+    render::{Image, Rgb},
+};
+
+/// Size of the LZW dictionary, codes being at most 12 bits long.
+const MAX_CODES: u16 = 1 << 12;
+
+/// GIF file showing `frames` in a loop, each one for `delay` hundredths of a second.
+pub fn encode(frames: &[Image], delay: u16) -> Result<Vec<u8>, Error> {
+    let size = frames
+        .first()
+        .ok_or("A GIF needs at least one frame")?
+        .size();
+    if frames.iter().any(|frame| frame.size() != size) {
+        return Err("Every frame of a GIF should have the same size".into());
+    }
+    if size.x > u16::MAX as i32 || size.y > u16::MAX as i32 {
+        return Err(format!("A {size} image is too large for a GIF").into());
+    }
+
+    let palette = Palette::new(frames)?;
+    let mut gif = b"GIF89a".to_vec();
+
+    // Logical screen, followed by the global colour table
+    push_size(&mut gif, size);
+    gif.push(0b1111_0000 | (palette.bits - 1));
+    gif.extend([0, 0]);
+    for index in 0..1 << palette.bits {
+        let Rgb(r, g, b) = palette.colors.get(index).copied().unwrap_or(Rgb::BLACK);
+        gif.extend([r, g, b]);
+    }
+
+    // Loops forever
+    gif.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    for frame in frames {
+        // Graphic control: every frame stays until the next one, without transparency
+        gif.extend([0x21, 0xf9, 4, 0b0000_0100]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0, 0]);
+
+        gif.push(0x2c);
+        push_size(&mut gif, Pair::ZERO);
+        push_size(&mut gif, size);
+        gif.push(0);
+
+        let indices: Vec<u8> = frame.pixels().iter().map(|c| palette.index[c]).collect();
+        let min_code_size = palette.bits.max(2);
+        gif.push(min_code_size);
+        for block in lzw(&indices, min_code_size).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0);
+    }
+
+    gif.push(0x3b);
+    Ok(gif)
+}
+
+fn push_size(gif: &mut Vec<u8>, size: Pair) {
+    gif.extend((size.x as u16).to_le_bytes());
+    gif.extend((size.y as u16).to_le_bytes());
+}
+
+struct Palette {
+    colors: Vec<Rgb>,
+    index: HashMap<Rgb, u8>,
+    /// The colour table holds `2^bits` colours.
+    bits: u8,
+}
+
+impl Palette {
+    fn new(frames: &[Image]) -> Result<Self, Error> {
+        let mut colors = Vec::new();
+        let mut index = HashMap::new();
+        for &color in frames.iter().flat_map(|frame| frame.pixels().iter()) {
+            if let Entry::Vacant(entry) = index.entry(color) {
+                if colors.len() == 256 {
+                    return Err("GIF frames can't use more than 256 colours".into());
+                }
+                entry.insert(colors.len() as u8);
+                colors.push(color);
+            }
+        }
+        let mut bits = 1;
+        while 1 << bits < colors.len() {
+            bits += 1;
+        }
+        Ok(Self {
+            colors,
+            index,
+            bits,
+        })
+    }
+}
+
+/// Packs codes least significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    current: u32,
+    len: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.current |= (code as u32) << self.len;
+        self.len += size;
+        while self.len >= 8 {
+            self.bytes.push(self.current as u8);
+            self.current >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.current as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Variable-length LZW compression of palette indices, as GIF decoders expect it: codes grow by
+/// a bit as soon as the next dictionary entry doesn't fit, and the dictionary is cleared once
+/// it is full.
+fn lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1 << min_code_size;
+    let end = clear + 1;
+
+    let mut output = BitWriter::default();
+    let mut code_size = min_code_size + 1;
+    let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = end + 1;
+
+    output.write(clear, code_size);
+    let Some((&first, rest)) = indices.split_first() else {
+        output.write(end, code_size);
+        return output.finish();
+    };
+
+    let mut current = first as u16;
+    for &index in rest {
+        if let Some(&code) = dictionary.get(&(current, index)) {
+            current = code;
+            continue;
+        }
+        output.write(current, code_size);
+        dictionary.insert((current, index), next_code);
+        if next_code >= 1 << code_size {
+            code_size += 1;
+        }
+        next_code += 1;
+        if next_code == MAX_CODES {
+            output.write(clear, code_size);
+            dictionary.clear();
+            code_size = min_code_size + 1;
+            next_code = end + 1;
+        }
+        current = index as u16;
+    }
+    output.write(current, code_size);
+
+    // The decoder adds one last entry before reading the end code
+    if next_code >= 1 << code_size {
+        code_size += 1;
+    }
+    output.write(end, code_size);
+    output.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    /// Reference decoder, following the GIF specification.
+    fn unlzw(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1u16 << min_code_size;
+        let end = clear + 1;
+        // The clear and end codes have no entry of their own
+        let reset = || -> Vec<Vec<u8>> {
+            (0..clear)
+                .map(|i| vec![i as u8])
+                .chain([vec![], vec![]])
+                .collect()
+        };
+
+        let mut table = reset();
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut output = Vec::new();
+        let (mut bits, mut len, mut position) = (0u32, 0u8, 0);
+
+        loop {
+            while len < code_size {
+                bits |= (bytes[position] as u32) << len;
+                position += 1;
+                len += 8;
+            }
+            let code = (bits & ((1 << code_size) - 1)) as u16;
+            bits >>= code_size;
+            len -= code_size;
+
+            if code == clear {
+                table = reset();
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return output;
+            }
+            let entry = match (&previous, table.get(code as usize)) {
+                (_, Some(entry)) => entry.clone(),
+                (Some(previous), None) => [previous.as_slice(), &previous[..1]].concat(),
+                (None, None) => panic!("Unknown first code {code}"),
+            };
+            output.extend(&entry);
+            if let Some(previous) = previous {
+                if table.len() < MAX_CODES as usize {
+                    table.push([previous.as_slice(), &entry[..1]].concat());
+                    if table.len() == 1 << code_size && code_size < 12 {
+                        code_size += 1;
+                    }
+                }
+            }
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        // Enough values for the dictionary to be cleared a few times
+        let mut state = 7u32;
+        let noise: Vec<u8> = (0..20_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                (state >> 16) as u8 % 5
+            })
+            .collect();
+        let runs: Vec<u8> = (0..3000).map(|i| (i / 7 % 3) as u8).collect();
+
+        for (indices, min_code_size) in [(&noise, 3), (&runs, 2), (&vec![], 2), (&vec![1], 2)] {
+            assert_eq!(&unlzw(&lzw(indices, min_code_size), min_code_size), indices);
+        }
+    }
+
+    #[test]
+    fn layout() {
+        let frame = |on| Image::from(Grid::from_fn(Pair { x: 3, y: 2 }, |_| on));
+        let gif = encode(&[frame(Rgb::WHITE), frame(Rgb(0, 0, 255))], 10).unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(&gif[6..10], [3, 0, 2, 0]);
+        // Two colours, in a table of two
+        assert_eq!(gif[10], 0b1111_0000);
+        assert_eq!(&gif[13..19], [255, 255, 255, 0, 0, 255]);
+        assert_eq!(gif.iter().filter(|&&b| b == 0x2c).count(), 2);
+        assert_eq!(gif.last(), Some(&0x3b));
+
+        let small = Image::from(Grid::filled(Pair { x: 1, y: 1 }, Rgb::BLACK));
+        assert!(encode(&[frame(Rgb::WHITE), small], 10).is_err());
+        assert!(encode(&[], 10).is_err());
+    }
+}
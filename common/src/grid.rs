@@ -0,0 +1,337 @@
+use std::{
+    borrow::Borrow,
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use crate::{error::Error, pair::Pair, rect::Rect};
+
+/// Dense 2D grid stored row by row, `(0, 0)` being the top left cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    size: Pair,
+    buffer: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row-major order.
+    pub fn new(size: Pair, buffer: Vec<T>) -> Result<Self, Error> {
+        if size.x < 0 || size.y < 0 || buffer.len() != (size.x * size.y) as usize {
+            return Err(format!("{} cells can't fill a {size} grid", buffer.len()).into());
+        }
+        Ok(Self { size, buffer })
+    }
+
+    pub fn from_fn(size: Pair, mut f: impl FnMut(Pair) -> T) -> Self {
+        let buffer = positions(size).map(&mut f).collect();
+        Self { size, buffer }
+    }
+
+    pub fn filled(size: Pair, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(size, |_| value.clone())
+    }
+
+    /// Parses one cell per character, every line being a row of the same width.
+    pub fn parse(
+        lines: impl IntoIterator<Item = impl Borrow<str>>,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, Error> {
+        let mut buffer = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for (line, number) in lines.into_iter().zip(1..) {
+            let line = line.borrow();
+            let before = buffer.len();
+
+            for (index, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    Error::new(format!("Unexpected character {c:?}"))
+                        .with_span(line, index..index + c.len_utf8())
+                        .at_line(number, line)
+                })?;
+                buffer.push(value);
+            }
+
+            let row_width = (buffer.len() - before) as i32;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(
+                        Error::new(format!("Row has {row_width} cells, expected {width}"))
+                            .at_line(number, line),
+                    );
+                }
+                _ => {}
+            }
+            height += 1;
+        }
+
+        let width = width.ok_or("Empty grid")?;
+        Ok(Self {
+            size: Pair {
+                x: width,
+                y: height,
+            },
+            buffer,
+        })
+    }
+
+    pub fn size(&self) -> Pair {
+        self.size
+    }
+
+    pub fn width(&self) -> i32 {
+        self.size.x
+    }
+
+    pub fn height(&self) -> i32 {
+        self.size.y
+    }
+
+    /// Area covered by the grid.
+    pub fn rect(&self) -> Rect {
+        Rect::from_size(self.size)
+    }
+
+    pub fn contains(&self, position: Pair) -> bool {
+        position.within(self.size)
+    }
+
+    fn index_of(&self, position: Pair) -> Option<usize> {
+        self.contains(position)
+            .then(|| (position.y * self.size.x + position.x) as usize)
+    }
+
+    pub fn get(&self, position: Pair) -> Option<&T> {
+        self.buffer.get(self.index_of(position)?)
+    }
+
+    pub fn get_mut(&mut self, position: Pair) -> Option<&mut T> {
+        let index = self.index_of(position)?;
+        self.buffer.get_mut(index)
+    }
+
+    /// Replaces the cell at `position`, returning the previous value, or `None` when out of bounds.
+    pub fn set(&mut self, position: Pair, value: T) -> Option<T> {
+        self.get_mut(position)
+            .map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Cells in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.buffer.iter()
+    }
+
+    /// Every position of the grid, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pair> {
+        positions(self.size)
+    }
+
+    /// Cells along with their position, in row-major order.
+    pub fn cells(&self) -> impl Iterator<Item = (Pair, &T)> {
+        self.positions().zip(self.buffer.iter())
+    }
+
+    pub fn row(&self, y: i32) -> Option<&[T]> {
+        (0..self.size.y).contains(&y).then(|| {
+            let start = (y * self.size.x) as usize;
+            &self.buffer[start..start + self.size.x as usize]
+        })
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.size.y).filter_map(|y| self.row(y))
+    }
+
+    /// Cells of the column `x`, from top to bottom; empty when out of bounds.
+    pub fn column(&self, x: i32) -> impl Iterator<Item = &T> {
+        let height = if (0..self.size.x).contains(&x) {
+            self.size.y
+        } else {
+            0
+        };
+        (0..height).map(move |y| &self[Pair { x, y }])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.size.x).map(|x| self.column(x))
+    }
+
+    /// Orthogonal neighbours of `position` within the grid.
+    pub fn neighbors4(&self, position: Pair) -> impl Iterator<Item = (Pair, &T)> {
+        self.existing(position.neighbors4())
+    }
+
+    /// Orthogonal and diagonal neighbours of `position` within the grid.
+    pub fn neighbors8(&self, position: Pair) -> impl Iterator<Item = (Pair, &T)> {
+        self.existing(position.neighbors8())
+    }
+
+    fn existing<const N: usize>(&self, positions: [Pair; N]) -> impl Iterator<Item = (Pair, &T)> {
+        positions
+            .into_iter()
+            .filter_map(|position| Some((position, self.get(position)?)))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            size: self.size,
+            buffer: self.buffer.iter().map(f).collect(),
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let size = Pair {
+            x: self.size.y,
+            y: self.size.x,
+        };
+        Self::from_fn(size, |Pair { x, y }| self[Pair { x: y, y: x }].clone())
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let size = Pair {
+            x: self.size.y,
+            y: self.size.x,
+        };
+        let height = self.size.y;
+        Self::from_fn(size, |Pair { x, y }| {
+            self[Pair {
+                x: y,
+                y: height - 1 - x,
+            }]
+            .clone()
+        })
+    }
+
+    /// Rotates the grid a quarter turn counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let size = Pair {
+            x: self.size.y,
+            y: self.size.x,
+        };
+        let width = self.size.x;
+        Self::from_fn(size, |Pair { x, y }| {
+            self[Pair {
+                x: width - 1 - y,
+                y: x,
+            }]
+            .clone()
+        })
+    }
+}
+
+fn positions(size: Pair) -> impl Iterator<Item = Pair> {
+    (0..size.y).flat_map(move |y| (0..size.x).map(move |x| Pair { x, y }))
+}
+
+impl<T> Index<Pair> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Pair) -> &T {
+        match self.get(position) {
+            Some(cell) => cell,
+            None => panic!("{position} is out of a {} grid", self.size),
+        }
+    }
+}
+
+impl<T> IndexMut<Pair> for Grid<T> {
+    fn index_mut(&mut self, position: Pair) -> &mut T {
+        let size = self.size;
+        match self.get_mut(position) {
+            Some(cell) => cell,
+            None => panic!("{position} is out of a {size} grid"),
+        }
+    }
+}
+
+/// Rows of cells, without separator between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(lines: &str) -> Grid<u32> {
+        Grid::parse(lines.lines(), |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.size(), Pair { x: 3, y: 2 });
+        assert_eq!(grid.get(Pair { x: 2, y: 1 }), Some(&6));
+        assert_eq!(grid.get(Pair { x: 3, y: 1 }), None);
+        assert_eq!(grid.get(Pair { x: 0, y: -1 }), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Grid::parse("12\n3x".lines(), |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.span(), Some(1..2));
+
+        let error = Grid::parse("12\n345".lines(), |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(error.line(), Some(2));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits("123\n456");
+        let rows: Vec<_> = grid.rows().collect();
+        assert_eq!(rows, [[1, 2, 3], [4, 5, 6]]);
+        let columns: Vec<Vec<_>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, [[1, 4], [2, 5], [3, 6]]);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = digits("123\n456\n789");
+        let corner: Vec<_> = grid
+            .neighbors4(Pair { x: 0, y: 0 })
+            .map(|(_, &v)| v)
+            .collect();
+        assert_eq!(corner, [2, 4]);
+        let center: Vec<_> = grid
+            .neighbors8(Pair { x: 1, y: 1 })
+            .map(|(_, &v)| v)
+            .collect();
+        assert_eq!(center, [1, 2, 3, 6, 9, 8, 7, 4]);
+    }
+
+    #[test]
+    fn transformations() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.transpose(), digits("14\n25\n36"));
+        assert_eq!(grid.rotate_cw(), digits("41\n52\n63"));
+        assert_eq!(grid.rotate_ccw(), digits("36\n25\n14"));
+        assert_eq!(grid.map(|v| v % 2).to_string(), "101\n010");
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    fs::File,
+    io::{stdin, BufRead, BufReader, Read},
+};
+
+use flate2::bufread::MultiGzDecoder;
+
+use crate::error::{Context, Error};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Puzzle input read from a file or stdin, transparently decompressed when gzipped.
+pub struct Input {
+    name: String,
+    reader: Box<dyn BufRead>,
+}
+
+impl Input {
+    /// Opens the file at `path`, or stdin when `path` is `-`.
+    pub fn open(path: &str) -> Result<Self, Error> {
+        if path == "-" {
+            return Self::stdin();
+        }
+        let file = File::open(path).context(format!("Couldn't open {path}"))?;
+        Self::from_reader(path, file)
+    }
+
+    pub fn stdin() -> Result<Self, Error> {
+        Self::from_reader("stdin", stdin())
+    }
+
+    pub fn from_reader(
+        name: impl Into<String>,
+        reader: impl Read + 'static,
+    ) -> Result<Self, Error> {
+        let name = name.into();
+        let mut reader = BufReader::new(reader);
+        let is_gzip = reader
+            .fill_buf()
+            .context(format!("Couldn't read {name}"))?
+            .starts_with(&GZIP_MAGIC);
+
+        let reader: Box<dyn BufRead> = if is_gzip {
+            Box::new(BufReader::new(MultiGzDecoder::new(reader)))
+        } else {
+            Box::new(reader)
+        };
+
+        Ok(Self { name, reader })
+    }
+
+    /// Lines of the input, without their `\n` or `\r\n` ending.
+    pub fn lines(self) -> Lines {
+        Lines {
+            input: self,
+            number: 0,
+            done: false,
+        }
+    }
+
+    /// Whole input, with line endings normalised to `\n`.
+    pub fn read_to_string(self) -> Result<String, Error> {
+        let mut text = String::new();
+        for line in self.lines() {
+            text.push_str(&line?);
+            text.push('\n');
+        }
+        Ok(text)
+    }
+}
+
+pub struct Lines {
+    input: Input,
+    number: usize,
+    done: bool,
+}
+
+impl Iterator for Lines {
+    type Item = Result<String, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut buffer = Vec::new();
+        match self.input.reader.read_until(b'\n', &mut buffer) {
+            Ok(0) => {
+                self.done = true;
+                None
+            }
+            Ok(_) => {
+                self.number += 1;
+                if buffer.ends_with(b"\n") {
+                    buffer.pop();
+                }
+                if buffer.ends_with(b"\r") {
+                    buffer.pop();
+                }
+                Some(String::from_utf8(buffer).map_err(|error| {
+                    let line = String::from_utf8_lossy(error.as_bytes()).into_owned();
+                    let start = error.utf8_error().valid_up_to();
+                    Error::new(format!("Invalid UTF-8 in {}", self.input.name))
+                        .with_span(&line, start..start + char::REPLACEMENT_CHARACTER.len_utf8())
+                        .at_line(self.number, &line)
+                        .with_source(error)
+                }))
+            }
+            Err(error) => {
+                self.done = true;
+                Some(Err(
+                    Error::from(error).context(format!("Couldn't read {}", self.input.name))
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::{Cursor, Write};
+
+    fn lines(bytes: Vec<u8>) -> Vec<Result<String, Error>> {
+        Input::from_reader("test", Cursor::new(bytes))
+            .unwrap()
+            .lines()
+            .collect()
+    }
+
+    #[test]
+    fn crlf() {
+        let text = Input::from_reader("test", Cursor::new(b"30373\r\n25512\r\n\r\nend"))
+            .unwrap()
+            .read_to_string()
+            .unwrap();
+        assert_eq!(text, "30373\n25512\n\nend\n");
+    }
+
+    #[test]
+    fn gzip() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"R 4\nU 4\n").unwrap();
+        let lines = lines(encoder.finish().unwrap());
+        let lines: Vec<_> = lines.into_iter().map(Result::unwrap).collect();
+        assert_eq!(lines, ["R 4", "U 4"]);
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let lines = lines(b"noop\nadd\xffx 3\n".to_vec());
+        assert!(lines[0].is_ok());
+        let error = lines[1].as_ref().unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.span(), Some(3..6));
+    }
+}
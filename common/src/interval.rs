@@ -0,0 +1,287 @@
+//! Intervals of integers, and sets of them kept merged.
+
+use std::ops::{Add, Range, RangeInclusive, Sub};
+
+/// Integer type usable as an interval endpoint.
+pub trait Endpoint: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + From<u8> {}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8>> Endpoint for T {}
+
+/// Values from `start` included to `end` excluded, empty when `end` isn't after `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Endpoint> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// Values from `first` to `last`, both included.
+    ///
+    /// # Panics
+    ///
+    /// When `last` is the largest value of `T`, as the end would overflow, in debug builds. Release
+    /// builds wrap it around to an empty or wrong interval instead.
+    pub fn inclusive(first: T, last: T) -> Self {
+        Self::new(first, last + T::from(1))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// Number of values in the interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::from(0)
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Whether every value of `other` is in this interval.
+    pub fn covers(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    /// Whether some value is in both intervals.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Values in both intervals, possibly none.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Values in either interval, `None` when there is a gap between them.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        (self.start <= other.end && other.start <= self.end)
+            .then(|| Self::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// Values of this interval not in `other`: the parts before and after it, each one possibly
+    /// empty. An empty part is the empty interval at the start of this one for the first part,
+    /// at its end for the second.
+    pub fn difference(&self, other: &Self) -> [Self; 2] {
+        if other.is_empty() {
+            return [*self, Self::new(self.end, self.end)];
+        }
+        // Clamped into this interval, or onto its start when it is empty
+        let clamp = |value: T| value.min(self.end).max(self.start);
+        let before_end = clamp(other.start);
+        let after_start = clamp(other.end).min(self.end);
+        [
+            Self::new(self.start, before_end),
+            Self::new(after_start, self.end),
+        ]
+    }
+}
+
+impl<T: Endpoint> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+/// Panics on `..=T::MAX` in debug builds, like [`Interval::inclusive`].
+impl<T: Endpoint> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        Self::inclusive(*range.start(), *range.end())
+    }
+}
+
+/// Union of intervals, stored as sorted intervals neither overlapping nor touching each other.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Endpoint> RangeSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Disjoint intervals making up the set, in increasing order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::from(0), |total, interval| total + interval.len())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let after = self.intervals.partition_point(|i| i.start <= value);
+        after > 0 && self.intervals[after - 1].contains(value)
+    }
+
+    /// Adds every value of `interval`, merging it with the intervals it overlaps or touches.
+    pub fn insert(&mut self, interval: impl Into<Interval<T>>) {
+        let mut interval = interval.into();
+        if interval.is_empty() {
+            return;
+        }
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        for merged in &self.intervals[first..last] {
+            interval = interval.union(merged).expect("Intervals should touch");
+        }
+        self.intervals.splice(first..last, [interval]);
+    }
+
+    /// Takes every value of `interval` out of the set.
+    pub fn remove(&mut self, interval: impl Into<Interval<T>>) {
+        let interval = interval.into();
+        if interval.is_empty() {
+            return;
+        }
+        let first = self.intervals.partition_point(|i| i.end <= interval.start);
+        let last = self.intervals.partition_point(|i| i.start < interval.end);
+        let remaining: Vec<_> = self.intervals[first..last]
+            .iter()
+            .flat_map(|overlapped| overlapped.difference(&interval))
+            .filter(|part| !part.is_empty())
+            .collect();
+        self.intervals.splice(first..last, remaining);
+    }
+
+    /// Values in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = Self::new();
+        let (mut a, mut b) = (self.intervals.iter(), other.intervals.iter());
+        let (mut left, mut right) = (a.next(), b.next());
+        while let (Some(l), Some(r)) = (left, right) {
+            let both = l.intersection(r);
+            if !both.is_empty() {
+                intersection.intervals.push(both);
+            }
+            if l.end < r.end {
+                left = a.next();
+            } else {
+                right = b.next();
+            }
+        }
+        intersection
+    }
+}
+
+impl<T: Endpoint> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Endpoint, I: Into<Interval<T>>> FromIterator<I> for RangeSet<T> {
+    fn from_iter<It: IntoIterator<Item = I>>(intervals: It) -> Self {
+        let mut set = Self::new();
+        set.extend(intervals);
+        set
+    }
+}
+
+impl<T: Endpoint, I: Into<Interval<T>>> Extend<I> for RangeSet<T> {
+    fn extend<It: IntoIterator<Item = I>>(&mut self, intervals: It) {
+        for interval in intervals {
+            self.insert(interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals() {
+        let a = Interval::inclusive(2, 6);
+        let b = Interval::from(4..9);
+        assert_eq!(a, Interval::new(2, 7));
+        assert_eq!(a.len(), 5);
+        assert!(a.contains(6) && !a.contains(7));
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&Interval::new(7, 9)));
+        assert!(a.covers(&Interval::inclusive(3, 6)) && !a.covers(&b));
+        assert_eq!(a.intersection(&b), Interval::new(4, 7));
+        assert!(a.intersection(&Interval::new(8, 9)).is_empty());
+        assert_eq!(a.union(&b), Some(Interval::new(2, 9)));
+        assert_eq!(a.union(&Interval::new(7, 8)), Some(Interval::new(2, 8)));
+        assert_eq!(a.union(&Interval::new(8, 9)), None);
+        assert_eq!(a.difference(&b), [Interval::new(2, 4), Interval::new(7, 7)]);
+        assert_eq!(
+            a.difference(&Interval::new(0, 3)),
+            [Interval::new(2, 2), Interval::new(3, 7)]
+        );
+        assert_eq!(
+            a.difference(&Interval::new(3, 5)),
+            [Interval::new(2, 3), Interval::new(5, 7)]
+        );
+        let empty = Interval::new(5, 3);
+        assert_eq!(a.difference(&empty), [a, Interval::new(7, 7)]);
+        assert_eq!(
+            empty.difference(&a),
+            [Interval::new(5, 5), Interval::new(3, 3)]
+        );
+        assert_eq!(Interval::new(5, 3).len(), 0);
+    }
+
+    #[test]
+    fn merged_set() {
+        let mut set: RangeSet<i32> = [1..3, 10..12, 5..6].into_iter().collect();
+        assert_eq!(set.intervals().len(), 3);
+        set.insert(3..5);
+        assert_eq!(
+            set.intervals(),
+            [Interval::new(1, 6), Interval::new(10, 12)]
+        );
+        set.insert(-4..=20);
+        assert_eq!(set.intervals(), [Interval::new(-4, 21)]);
+        assert_eq!(set.len(), 25);
+
+        set.remove(0..2);
+        set.remove(5..=7);
+        assert_eq!(
+            set.intervals(),
+            [
+                Interval::new(-4, 0),
+                Interval::new(2, 5),
+                Interval::new(8, 21)
+            ]
+        );
+        assert!(set.contains(4) && !set.contains(5) && !set.contains(21));
+        assert_eq!(set.len(), 20);
+
+        let other: RangeSet<i32> = [-10..-2, 4..9].into_iter().collect();
+        let both = set.intersection(&other);
+        assert_eq!(
+            both.intervals(),
+            [
+                Interval::new(-4, -2),
+                Interval::new(4, 5),
+                Interval::new(8, 9)
+            ]
+        );
+    }
+}
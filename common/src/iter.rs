@@ -0,0 +1,202 @@
+//! Iterator adapters missing from stable Rust, available on every iterator through [`IterExt`].
+
+use std::collections::VecDeque;
+
+pub trait IterExt: Iterator + Sized {
+    /// Groups the items by arrays of `N`, the last incomplete group being kept aside as the
+    /// [`FixedChunks::remainder`], like the nightly `array_chunks`.
+    fn fixed_chunks<const N: usize>(self) -> FixedChunks<Self, N> {
+        assert!(N > 0, "Chunks should not be empty");
+        FixedChunks {
+            iter: self,
+            remainder: Vec::new(),
+        }
+    }
+
+    /// Groups the items by vectors of `size`, the last one being shorter when they run out.
+    fn chunks(self, size: usize) -> Chunks<Self> {
+        assert!(size > 0, "Chunks should not be empty");
+        Chunks { iter: self, size }
+    }
+
+    /// Every run of `N` consecutive items, cloning each of them `N` times.
+    fn windows<const N: usize>(self) -> Windows<Self, N>
+    where
+        Self::Item: Clone,
+    {
+        assert!(N > 0, "Windows should not be empty");
+        Windows {
+            iter: self,
+            window: VecDeque::with_capacity(N),
+        }
+    }
+
+    /// Groups of items between those matching `is_separator`, which are dropped. Consecutive
+    /// separators give empty groups, as blank lines would for [`str::split`].
+    fn split_by<F>(self, is_separator: F) -> SplitBy<Self, F>
+    where
+        F: FnMut(&Self::Item) -> bool,
+    {
+        SplitBy {
+            iter: self,
+            is_separator,
+            done: false,
+        }
+    }
+
+    /// Folds the values up to the first error, which is returned instead.
+    fn fold_ok<T, E, B>(self, init: B, mut f: impl FnMut(B, T) -> B) -> Result<B, E>
+    where
+        Self: Iterator<Item = Result<T, E>>,
+    {
+        let mut accumulator = init;
+        for item in self {
+            accumulator = f(accumulator, item?);
+        }
+        Ok(accumulator)
+    }
+}
+
+impl<I: Iterator> IterExt for I {}
+
+pub struct FixedChunks<I: Iterator, const N: usize> {
+    iter: I,
+    remainder: Vec<I::Item>,
+}
+
+impl<I: Iterator, const N: usize> FixedChunks<I, N> {
+    /// Items left over once the iterator is exhausted, fewer than `N`.
+    pub fn remainder(&self) -> &[I::Item] {
+        &self.remainder
+    }
+}
+
+impl<I: Iterator, const N: usize> Iterator for FixedChunks<I, N> {
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = Vec::with_capacity(N);
+        chunk.extend(self.iter.by_ref().take(N));
+        match chunk.try_into() {
+            Ok(chunk) => Some(chunk),
+            Err(incomplete) => {
+                self.remainder = incomplete;
+                None
+            }
+        }
+    }
+}
+
+pub struct Chunks<I> {
+    iter: I,
+    size: usize,
+}
+
+impl<I: Iterator> Iterator for Chunks<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk: Vec<_> = self.iter.by_ref().take(self.size).collect();
+        (!chunk.is_empty()).then_some(chunk)
+    }
+}
+
+pub struct Windows<I: Iterator, const N: usize> {
+    iter: I,
+    window: VecDeque<I::Item>,
+}
+
+impl<I: Iterator, const N: usize> Iterator for Windows<I, N>
+where
+    I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.len() == N {
+            self.window.pop_front();
+        }
+        while self.window.len() < N {
+            self.window.push_back(self.iter.next()?);
+        }
+        Some(std::array::from_fn(|index| self.window[index].clone()))
+    }
+}
+
+pub struct SplitBy<I, F> {
+    iter: I,
+    is_separator: F,
+    done: bool,
+}
+
+impl<I, F> Iterator for SplitBy<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut group = Vec::new();
+        for item in self.iter.by_ref() {
+            if (self.is_separator)(&item) {
+                return Some(group);
+            }
+            group.push(item);
+        }
+        self.done = true;
+        Some(group)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_chunks() {
+        let mut chunks = (1..=7).fixed_chunks::<3>();
+        assert_eq!(chunks.next(), Some([1, 2, 3]));
+        assert_eq!(chunks.next(), Some([4, 5, 6]));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.remainder(), [7]);
+    }
+
+    #[test]
+    fn chunks() {
+        let chunks: Vec<_> = (1..=5).chunks(2).collect();
+        assert_eq!(chunks, [vec![1, 2], vec![3, 4], vec![5]]);
+    }
+
+    #[test]
+    fn windows() {
+        let windows: Vec<_> = "abcd".chars().windows::<3>().collect();
+        assert_eq!(windows, [['a', 'b', 'c'], ['b', 'c', 'd']]);
+        assert_eq!("ab".chars().windows::<3>().count(), 0);
+    }
+
+    #[test]
+    fn split_by() {
+        let groups: Vec<_> = "1\n2\n\n3\n"
+            .lines()
+            .split_by(|line| line.is_empty())
+            .collect();
+        assert_eq!(groups, [vec!["1", "2"], vec!["3"]]);
+        let groups: Vec<_> = [0, 1, 0, 0].into_iter().split_by(|&x| x == 0).collect();
+        assert_eq!(groups, [vec![], vec![1], vec![], vec![]]);
+    }
+
+    #[test]
+    fn results() {
+        let numbers = ["1", "2", "3"].map(str::parse::<u32>);
+        assert_eq!(numbers.into_iter().fold_ok(1, |a, b| a * b), Ok(6));
+        assert!(["1", "x"]
+            .map(str::parse::<u32>)
+            .into_iter()
+            .fold_ok(0, |a, b| a + b)
+            .is_err());
+    }
+}
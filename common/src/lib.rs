@@ -1,17 +1,30 @@
+pub mod answer;
+pub mod bitset;
+pub mod error;
+pub mod fixtures;
+pub mod frames;
+pub mod gif;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod iter;
+pub mod log;
 pub mod pair;
+pub mod point;
+pub mod property;
+pub mod rect;
+pub mod render;
+pub mod report;
+pub mod rng;
+pub mod scan;
+pub mod search;
+pub mod sections;
+pub mod solution;
+pub mod sparse;
 
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
-pub fn lines_from_stdin() -> impl Iterator<Item = String> {
-    std::io::stdin().lines().map(|e| e.expect("Invalid line"))
-}
-
-pub fn lines_from_file(filename: &str) -> Result<impl Iterator<Item = String>, &'static str> {
-    let file = File::open(filename).or(Err("Couldn't open file."))?;
-    Ok(BufReader::new(file)
-        .lines()
-        .map(|e| e.expect("Invalid line")))
-}
+pub use answer::Answer;
+pub use error::Error;
+pub use grid::Grid;
+pub use input::Input;
+pub use solution::{DynSolution, Solution};
+pub use sparse::SparseGrid;
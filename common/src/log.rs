@@ -0,0 +1,124 @@
+//! Diagnostics written to stderr, filtered by level so that stdout only ever holds answers.
+//!
+//! The runner sets the level from `-q` and `-v`; solutions log through [`warn!`], [`info!`],
+//! [`debug!`] and [`trace!`], whose arguments are only formatted when the level is enabled.
+//!
+//! [`warn!`]: crate::warn
+//! [`info!`]: crate::info
+//! [`debug!`]: crate::debug
+//! [`trace!`]: crate::trace
+
+use std::{
+    fmt::{self, Display},
+    io::{self, Write},
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Warn,
+    Info,
+    /// Intermediate results, such as the size of a grid or a whole tree.
+    Debug,
+    /// Every step of a simulation.
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 4] = [Level::Warn, Level::Info, Level::Debug, Level::Trace];
+
+    /// Level shown by default, moved down by each `-q` and up by each `-v`. Warnings are always
+    /// shown.
+    pub fn from_verbosity(verbosity: i8) -> Self {
+        let index = (Level::Info as i8)
+            .saturating_add(verbosity)
+            .clamp(0, Level::Trace as i8);
+        Self::ALL[index as usize]
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Level::Warn => "warning",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        })
+    }
+}
+
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+
+/// Most verbose level written from now on.
+pub fn set_max_level(level: Level) {
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn max_level() -> Level {
+    Level::ALL[MAX_LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+/// Whether messages of `level` are written, to skip building costly ones.
+pub fn enabled(level: Level) -> bool {
+    level <= max_level()
+}
+
+/// Writes `message` to stderr, each of its lines prefixed with the level. Use the macros
+/// instead, which skip formatting disabled messages.
+pub fn write(level: Level, message: fmt::Arguments) {
+    let message = message.to_string();
+    let mut stderr = io::stderr().lock();
+    for line in message.lines() {
+        // Nowhere left to report a failing stderr
+        let _ = writeln!(stderr, "{level}: {line}");
+    }
+}
+
+/// Logs at `level` when it is enabled.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {{
+        let level = $level;
+        if $crate::log::enabled(level) {
+            $crate::log::write(level, format_args!($($arg)+));
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbosity() {
+        assert_eq!(Level::from_verbosity(0), Level::Info);
+        assert_eq!(Level::from_verbosity(-1), Level::Warn);
+        assert_eq!(Level::from_verbosity(-5), Level::Warn);
+        assert_eq!(Level::from_verbosity(2), Level::Trace);
+        assert_eq!(Level::from_verbosity(9), Level::Trace);
+        assert_eq!(Level::from_verbosity(i8::MAX), Level::Trace);
+        assert_eq!(Level::from_verbosity(i8::MIN), Level::Warn);
+        assert!(Level::Warn < Level::Debug);
+    }
+}
@@ -1,17 +1,38 @@
 use std::{
     fmt::Display,
-    ops::{Add, Sub},
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
 };
 
+use crate::{error::Error, point::Point2};
+
+/// 2D point with named fields. The arithmetic is the one of [`Point2`], which it converts to and
+/// from for free.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Pair {
     pub x: i32,
     pub y: i32,
 }
 
+impl From<Pair> for Point2 {
+    fn from(Pair { x, y }: Pair) -> Self {
+        Point2::new([x, y])
+    }
+}
+
+impl From<Point2> for Pair {
+    fn from(point: Point2) -> Self {
+        Pair {
+            x: point[0],
+            y: point[1],
+        }
+    }
+}
+
+/// Same format as [`Point2`]: `(x, y)`.
 impl Display for Pair {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
+        Point2::from(*self).fmt(f)
     }
 }
 
@@ -19,10 +40,7 @@ impl Add for Pair {
     type Output = Pair;
 
     fn add(self, rhs: Pair) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
+        (Point2::from(self) + rhs.into()).into()
     }
 }
 
@@ -30,16 +48,102 @@ impl Sub for Pair {
     type Output = Pair;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
+        (Point2::from(self) - rhs.into()).into()
+    }
+}
+
+impl AddAssign for Pair {
+    fn add_assign(&mut self, rhs: Pair) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Pair {
+    fn sub_assign(&mut self, rhs: Pair) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Pair {
+    type Output = Pair;
+
+    fn neg(self) -> Self::Output {
+        (-Point2::from(self)).into()
+    }
+}
+
+impl Mul<i32> for Pair {
+    type Output = Pair;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        (Point2::from(self) * rhs).into()
+    }
+}
+
+impl Div<i32> for Pair {
+    type Output = Pair;
+
+    fn div(self, rhs: i32) -> Self::Output {
+        (Point2::from(self) / rhs).into()
+    }
+}
+
+/// Parses `x,y` as well as the `(x, y)` form written by [`Display`].
+impl FromStr for Pair {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.parse::<Point2>()?.into())
     }
 }
 
 impl Pair {
-    fn contains(&self, pair: &Pair) -> bool {
-        (0..self.x).contains(&pair.x) && (0..self.y).contains(&pair.y)
+    pub const ZERO: Pair = Pair { x: 0, y: 0 };
+
+    /// Whether this position lies in the rectangle from `(0, 0)` included to `size` excluded.
+    pub fn within(&self, size: Pair) -> bool {
+        Point2::from(*self).within(size.into())
+    }
+
+    /// Unit step towards the same quadrant, each coordinate being -1, 0 or 1.
+    pub fn signum(self) -> Pair {
+        Point2::from(self).signum().into()
+    }
+
+    /// Manhattan distance from the origin.
+    pub fn manhattan(self) -> i32 {
+        Point2::from(self).manhattan()
+    }
+
+    /// Chebyshev (king move) distance from the origin.
+    pub fn chebyshev(self) -> i32 {
+        Point2::from(self).chebyshev()
+    }
+
+    /// Quarter turn clockwise, with `y` pointing down: `UP` becomes `RIGHT`.
+    pub fn rotate_cw(self) -> Pair {
+        Pair {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    /// Quarter turn counter-clockwise, with `y` pointing down: `UP` becomes `LEFT`.
+    pub fn rotate_ccw(self) -> Pair {
+        Pair {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+
+    /// Orthogonal neighbours, clockwise from the one above.
+    pub fn neighbors4(self) -> [Pair; 4] {
+        DIRECTIONS4.map(|direction| self + direction)
+    }
+
+    /// Orthogonal and diagonal neighbours, clockwise from the top left one.
+    pub fn neighbors8(self) -> [Pair; 8] {
+        DIRECTIONS8.map(|direction| self + direction)
     }
 }
 
@@ -49,11 +153,33 @@ pub const LEFT: Direction = Pair { x: -1, y: 0 };
 pub const RIGHT: Direction = Pair { x: 1, y: 0 };
 pub const UP: Direction = Pair { x: 0, y: -1 };
 pub const DOWN: Direction = Pair { x: 0, y: 1 };
+pub const UP_LEFT: Direction = Pair { x: -1, y: -1 };
+pub const UP_RIGHT: Direction = Pair { x: 1, y: -1 };
+pub const DOWN_LEFT: Direction = Pair { x: -1, y: 1 };
+pub const DOWN_RIGHT: Direction = Pair { x: 1, y: 1 };
 
+/// Orthogonal directions, clockwise from `UP`.
+pub const DIRECTIONS4: [Direction; 4] = [UP, RIGHT, DOWN, LEFT];
+/// Orthogonal and diagonal directions, clockwise from `UP_LEFT`.
+pub const DIRECTIONS8: [Direction; 8] = [
+    UP_LEFT, UP, UP_RIGHT, RIGHT, DOWN_RIGHT, DOWN, DOWN_LEFT, LEFT,
+];
+
+/// Walks from `current` (excluded) in `direction`, until leaving the rectangle from `(0, 0)` to `size`.
 pub struct CoordsIter {
-    pub size: Pair,
-    pub current: Option<Pair>,
-    pub direction: Direction,
+    size: Pair,
+    current: Option<Pair>,
+    direction: Direction,
+}
+
+impl CoordsIter {
+    pub fn new(size: Pair, start: Pair, direction: Direction) -> Self {
+        Self {
+            size,
+            current: Some(start),
+            direction,
+        }
+    }
 }
 
 impl Iterator for CoordsIter {
@@ -62,7 +188,7 @@ impl Iterator for CoordsIter {
     fn next(&mut self) -> Option<Self::Item> {
         let current = self.current?;
         let new = current + self.direction;
-        self.current = if self.size.contains(&new) {
+        self.current = if new.within(self.size) {
             Some(new)
         } else {
             None
@@ -71,17 +197,104 @@ impl Iterator for CoordsIter {
     }
 }
 
+/// The `steps` positions after `start` in `direction`, without any bounds other than `steps`
+/// and stopping before the first one out of the `i32` range.
+pub fn ray(start: Pair, direction: Direction, steps: usize) -> impl Iterator<Item = Pair> {
+    let steps = i32::try_from(steps).unwrap_or(i32::MAX);
+    (1..=steps).map_while(move |step| {
+        let along = |start: i32, direction: i32| direction.checked_mul(step)?.checked_add(start);
+        Some(Pair {
+            x: along(start.x, direction.x)?,
+            y: along(start.y, direction.y)?,
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn north() {
-        let mut iter = CoordsIter {
-            size: Pair { x: 3, y: 5 },
-            current: Some(Pair { x: 2, y: 3 }),
-            direction: UP,
+    fn arithmetic() {
+        let mut pair = Pair { x: 3, y: -4 };
+        assert_eq!(-pair, Pair { x: -3, y: 4 });
+        assert_eq!(pair * 2, Pair { x: 6, y: -8 });
+        assert_eq!(pair / 2, Pair { x: 1, y: -2 });
+        assert_eq!(pair.signum(), Pair { x: 1, y: -1 });
+        assert_eq!(pair.manhattan(), 7);
+        assert_eq!(pair.chebyshev(), 4);
+        pair += RIGHT;
+        pair -= UP;
+        assert_eq!(pair, Pair { x: 4, y: -3 });
+    }
+
+    #[test]
+    fn rotations() {
+        assert_eq!(UP.rotate_cw(), RIGHT);
+        assert_eq!(RIGHT.rotate_cw(), DOWN);
+        assert_eq!(UP.rotate_ccw(), LEFT);
+        assert_eq!(LEFT.rotate_ccw(), DOWN);
+    }
+
+    #[test]
+    fn neighbors() {
+        let pair = Pair { x: 1, y: 1 };
+        assert_eq!(
+            pair.neighbors4(),
+            [(1, 0), (2, 1), (1, 2), (0, 1)].map(|(x, y)| Pair { x, y })
+        );
+        assert!(pair
+            .neighbors8()
+            .iter()
+            .all(|n| (*n - pair).chebyshev() == 1));
+        assert!(pair.within(Pair { x: 2, y: 2 }));
+        assert!(!pair.within(Pair { x: 1, y: 2 }));
+    }
+
+    #[test]
+    fn parsing() {
+        let pair = Pair { x: -2, y: 15 };
+        assert_eq!("-2,15".parse::<Pair>().unwrap(), pair);
+        assert_eq!(pair.to_string().parse::<Pair>().unwrap(), pair);
+        assert!("2;15".parse::<Pair>().is_err());
+        assert_eq!("(2, x)".parse::<Pair>().unwrap_err().span(), Some(4..5));
+    }
+
+    #[test]
+    fn diagonal() {
+        let iter = CoordsIter::new(Pair { x: 4, y: 3 }, Pair { x: 0, y: 2 }, UP_RIGHT);
+        let cells: Vec<_> = iter.collect();
+        assert_eq!(cells, [Pair { x: 1, y: 1 }, Pair { x: 2, y: 0 }]);
+    }
+
+    #[test]
+    fn unbounded_ray() {
+        let cells: Vec<_> = ray(Pair { x: 0, y: 0 }, DOWN_LEFT, 3).collect();
+        assert_eq!(
+            cells,
+            [(-1, 1), (-2, 2), (-3, 3)].map(|(x, y)| Pair { x, y })
+        );
+        assert_eq!(
+            ray(Pair::ZERO, RIGHT, usize::MAX).nth(4),
+            Some(Pair { x: 5, y: 0 })
+        );
+
+        let start = Pair {
+            x: i32::MAX - 2,
+            y: 0,
         };
+        assert_eq!(
+            ray(start, RIGHT, usize::MAX).last(),
+            Some(Pair { x: i32::MAX, y: 0 })
+        );
+        assert_eq!(ray(start, UP_LEFT, 5).count(), 5);
+        let far = Pair { x: 0, y: 1 << 30 };
+        assert_eq!(ray(Pair::ZERO, far, 10).count(), 1);
+    }
+
+    #[test]
+    fn north() {
+        let mut iter = CoordsIter::new(Pair { x: 3, y: 5 }, Pair { x: 2, y: 3 }, UP);
         for y in [2, 1, 0] {
             assert_eq!(iter.next(), Some(Pair { x: 2, y }));
         }
@@ -90,11 +303,7 @@ mod tests {
 
     #[test]
     fn south() {
-        let mut iter = CoordsIter {
-            size: Pair { x: 3, y: 5 },
-            current: Some(Pair { x: 1, y: 2 }),
-            direction: DOWN,
-        };
+        let mut iter = CoordsIter::new(Pair { x: 3, y: 5 }, Pair { x: 1, y: 2 }, DOWN);
         for y in [3, 4] {
             assert_eq!(iter.next(), Some(Pair { x: 1, y }));
         }
@@ -103,11 +312,7 @@ mod tests {
 
     #[test]
     fn east() {
-        let mut iter = CoordsIter {
-            size: Pair { x: 3, y: 5 },
-            current: Some(Pair { x: 0, y: 0 }),
-            direction: RIGHT,
-        };
+        let mut iter = CoordsIter::new(Pair { x: 3, y: 5 }, Pair { x: 0, y: 0 }, RIGHT);
         for x in [1, 2] {
             assert_eq!(iter.next(), Some(Pair { y: 0, x }))
         }
@@ -0,0 +1,296 @@
+use std::{
+    fmt::{self, Debug, Display},
+    hash::Hash,
+    ops::{Add, AddAssign, Div, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::error::Error;
+
+/// Signed integer usable as a [`Point`] coordinate.
+pub trait Coordinate:
+    Copy
+    + Ord
+    + Hash
+    + Debug
+    + Display
+    + FromStr
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
+
+macro_rules! coordinate {
+    ($($type:ty),*) => {
+        $(
+            impl Coordinate for $type {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    <$type>::abs(self)
+                }
+
+                fn signum(self) -> Self {
+                    <$type>::signum(self)
+                }
+            }
+        )*
+    };
+}
+
+coordinate!(i8, i16, i32, i64, i128, isize);
+
+/// Point or vector in `N` dimensions, the generalisation of [`Pair`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<const N: usize, T = i32>(pub [T; N]);
+
+pub type Point2<T = i32> = Point<2, T>;
+pub type Point3<T = i32> = Point<3, T>;
+
+impl<const N: usize, T: Coordinate> Point<N, T> {
+    pub const ZERO: Self = Point([T::ZERO; N]);
+
+    pub fn new(coords: [T; N]) -> Self {
+        Point(coords)
+    }
+
+    /// Unit vector along the axis `axis`.
+    pub fn unit(axis: usize) -> Self {
+        let mut unit = Self::ZERO;
+        unit.0[axis] = T::ONE;
+        unit
+    }
+
+    fn map(self, f: impl FnMut(T) -> T) -> Self {
+        Point(self.0.map(f))
+    }
+
+    fn zip(self, other: Self, mut f: impl FnMut(T, T) -> T) -> Self {
+        Point(std::array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+
+    /// Unit step towards the same orthant, each coordinate being -1, 0 or 1.
+    pub fn signum(self) -> Self {
+        self.map(T::signum)
+    }
+
+    /// Manhattan distance from the origin.
+    pub fn manhattan(self) -> T {
+        self.0.into_iter().map(T::abs).fold(T::ZERO, T::add)
+    }
+
+    /// Chebyshev (king move) distance from the origin.
+    pub fn chebyshev(self) -> T {
+        self.0.into_iter().map(T::abs).max().unwrap_or(T::ZERO)
+    }
+
+    /// Whether this point lies in the box from the origin included to `size` excluded.
+    pub fn within(&self, size: Self) -> bool {
+        (0..N).all(|i| T::ZERO <= self.0[i] && self.0[i] < size.0[i])
+    }
+
+    /// Whether this point lies in the box from `min` to `max`, both included.
+    pub fn between(&self, min: Self, max: Self) -> bool {
+        (0..N).all(|i| min.0[i] <= self.0[i] && self.0[i] <= max.0[i])
+    }
+
+    /// The `2 * N` points sharing a face with this one.
+    pub fn orthogonal_neighbors(self) -> impl Iterator<Item = Self> {
+        (0..N).flat_map(move |axis| [self - Self::unit(axis), self + Self::unit(axis)])
+    }
+
+    /// The `3^N - 1` points touching this one, diagonals included.
+    pub fn neighbors(self) -> impl Iterator<Item = Self> {
+        let count = 3usize.pow(N as u32);
+        (0..count)
+            .filter(move |&index| index != count / 2)
+            .map(move |mut index| {
+                let mut offset = Self::ZERO;
+                for coord in offset.0.iter_mut() {
+                    *coord = [-T::ONE, T::ZERO, T::ONE][index % 3];
+                    index /= 3;
+                }
+                self + offset
+            })
+    }
+}
+
+impl<const N: usize, T: Coordinate> Default for Point<N, T> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const N: usize, T> Index<usize> for Point<N, T> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize, T> IndexMut<usize> for Point<N, T> {
+    fn index_mut(&mut self, axis: usize) -> &mut T {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize, T: Coordinate> Add for Point<N, T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip(rhs, T::add)
+    }
+}
+
+impl<const N: usize, T: Coordinate> Sub for Point<N, T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip(rhs, T::sub)
+    }
+}
+
+impl<const N: usize, T: Coordinate> AddAssign for Point<N, T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize, T: Coordinate> SubAssign for Point<N, T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize, T: Coordinate> Neg for Point<N, T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(T::neg)
+    }
+}
+
+impl<const N: usize, T: Coordinate> Mul<T> for Point<N, T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        self.map(|coord| coord * rhs)
+    }
+}
+
+impl<const N: usize, T: Coordinate> Div<T> for Point<N, T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        self.map(|coord| coord / rhs)
+    }
+}
+
+/// Same format as [`Pair`]: `(x, y, z)`.
+impl<const N: usize, T: Display> Display for Point<N, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, coord) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{coord}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Parses `x,y,z` as well as the `(x, y, z)` form written by [`Display`].
+impl<const N: usize, T: Coordinate> FromStr for Point<N, T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let inner = trimmed
+            .strip_prefix('(')
+            .and_then(|inner| inner.strip_suffix(')'))
+            .unwrap_or(trimmed);
+
+        let mut point = Self::ZERO;
+        let mut values = inner.split(',');
+        for coord in point.0.iter_mut() {
+            let value = values
+                .next()
+                .ok_or_else(|| Error::at(format!("Expected {N} coordinates"), s, trimmed))?
+                .trim();
+            *coord = value
+                .parse()
+                .map_err(|e| Error::at("Couldn't parse coordinate", s, value).with_source(e))?;
+        }
+        if values.next().is_some() {
+            return Err(Error::at(format!("Expected {N} coordinates"), s, trimmed));
+        }
+        Ok(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point3::new([1, -2, 3]);
+        let b = Point3::new([4, 5, -6]);
+        assert_eq!(a + b, Point([5, 3, -3]));
+        assert_eq!(a - b, Point([-3, -7, 9]));
+        assert_eq!(-a * 2, Point([-2, 4, -6]));
+        assert_eq!((b - a).manhattan(), 19);
+        assert_eq!((b - a).chebyshev(), 9);
+        assert_eq!((b - a).signum(), Point([1, 1, -1]));
+    }
+
+    #[test]
+    fn wide_coordinates() {
+        let far = Point::<2, i64>::new([5_000_000_000, -1]);
+        assert_eq!(far.manhattan(), 5_000_000_001);
+    }
+
+    #[test]
+    fn neighbors() {
+        let origin = Point3::<i32>::ZERO;
+        assert_eq!(origin.orthogonal_neighbors().count(), 6);
+        assert!(origin.orthogonal_neighbors().all(|n| n.manhattan() == 1));
+        assert_eq!(origin.neighbors().count(), 26);
+        assert!(origin.neighbors().all(|n| n.chebyshev() == 1));
+    }
+
+    #[test]
+    fn containment() {
+        let size = Point3::new([2, 3, 4]);
+        assert!(Point([1, 2, 3]).within(size));
+        assert!(!Point([1, 3, 3]).within(size));
+        assert!(Point([-1, 0, 1]).between(Point([-1, -1, -1]), Point([1, 1, 1])));
+    }
+
+    #[test]
+    fn pair_conversion_and_parsing() {
+        use crate::pair::Pair;
+
+        let pair = Pair { x: 3, y: -1 };
+        let point = Point2::from(pair);
+        assert_eq!(point.to_string(), pair.to_string());
+        assert_eq!(Pair::from(point), pair);
+        assert_eq!("(1, 2, 3)".parse::<Point3>().unwrap(), Point([1, 2, 3]));
+        assert!("1,2".parse::<Point3>().is_err());
+        assert!("1,2,3,4".parse::<Point3>().is_err());
+    }
+}
@@ -0,0 +1,246 @@
+//! Differential property tests: a day's solution is run against a naive reference on generated
+//! inputs, and any disagreement is shrunk to the fewest lines still showing it.
+
+use std::{
+    cell::Cell,
+    fmt::{self, Display},
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+use crate::{answer::Answer, rng::Rng, solution::Solution};
+
+/// Generated inputs checked by [`assert_matches_reference`].
+pub const CASES: u64 = 64;
+
+/// Largest `size` given to the generator, sizes growing with the case so that the first
+/// failures tend to be small.
+pub const MAX_SIZE: usize = 120;
+
+/// Input on which the solution and the reference disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub seed: u64,
+    pub part: u8,
+    /// Lines of the generated input before shrinking.
+    pub generated_lines: usize,
+    pub input: String,
+    /// Answers of the reference and of the solution, `None` when it failed or panicked.
+    pub expected: Option<Answer>,
+    pub got: Option<Answer>,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let answer = |answer: &Option<Answer>| match answer {
+            Some(answer) => answer.to_string(),
+            None => "(failed)".to_string(),
+        };
+        writeln!(
+            f,
+            "Part {} disagrees with the reference on seed {}, shrunk from {} lines to:",
+            self.part, self.seed, self.generated_lines
+        )?;
+        writeln!(f, "{}", self.input.trim_end())?;
+        writeln!(f, "Expected:\n{}", answer(&self.expected))?;
+        write!(f, "Got:\n{}", answer(&self.got))
+    }
+}
+
+/// Panics with the smallest input found on which `part` of `S` doesn't give the same answer as
+/// `reference`, which returns `None` for inputs it can't solve.
+pub fn assert_matches_reference<S, A>(part: u8, reference: impl Fn(&str) -> Option<A>)
+where
+    S: Solution,
+    A: Into<Answer>,
+{
+    if let Some(mismatch) = find_mismatch::<S, A>(part, CASES, reference) {
+        panic!("{mismatch}");
+    }
+}
+
+/// First of `cases` generated inputs on which `part` of `S` and `reference` disagree, shrunk.
+///
+/// # Panics
+///
+/// When `reference` can't solve a generated input, since those are valid by construction and
+/// rejecting them on both sides would check nothing.
+pub fn find_mismatch<S, A>(
+    part: u8,
+    cases: u64,
+    reference: impl Fn(&str) -> Option<A>,
+) -> Option<Mismatch>
+where
+    S: Solution,
+    A: Into<Answer>,
+{
+    let run = |input: &str| {
+        let expected = quietly(|| reference(input).map(Into::into));
+        let got = quietly(|| {
+            let input = S::parse(input).ok()?;
+            match part {
+                1 => S::part1(&input).ok().map(Into::into),
+                _ => S::part2(&input).ok().map(Into::into),
+            }
+        });
+        (expected, got)
+    };
+
+    (0..cases).find_map(|seed| {
+        let mut rng = Rng::new(seed);
+        let size = 1 + (seed as usize * MAX_SIZE) / cases as usize;
+        let input = S::generate(size, &mut rng).expect("The day should have a generator");
+        let (expected, got) = run(&input);
+        assert!(
+            expected.is_some(),
+            "The reference couldn't solve the input generated from seed {seed}:\n{}",
+            input.trim_end()
+        );
+        if expected == got {
+            return None;
+        }
+
+        // Shrinks towards the same kind of failure, rather than any input one side rejects
+        let kind = (expected.is_some(), got.is_some());
+        let generated_lines = input.lines().count();
+        let input = shrink(&input, |candidate| {
+            let (expected, got) = run(candidate);
+            expected != got && (expected.is_some(), got.is_some()) == kind
+        });
+        let (expected, got) = run(&input);
+        Some(Mismatch {
+            seed,
+            part,
+            generated_lines,
+            input,
+            expected,
+            got,
+        })
+    })
+}
+
+/// Removes lines of `input` for as long as it still `fails`, first by large chunks then one by
+/// one, until removing any single line would make it pass.
+pub fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut lines: Vec<&str> = input.lines().collect();
+    let mut chunk = lines.len().div_ceil(2).max(1);
+    loop {
+        let mut start = 0;
+        let mut removed = false;
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate: Vec<&str> = [&lines[..start], &lines[end..]].concat();
+            if fails(&join(&candidate)) {
+                lines = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+        if chunk == 1 && !removed {
+            return join(&lines);
+        }
+        if !removed {
+            chunk = chunk.div_ceil(2);
+        }
+    }
+}
+
+fn join(lines: &[&str]) -> String {
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+thread_local! {
+    /// Whether panics on this thread are expected, and kept off stderr.
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Result of `f`, or `None` when it panicked, without the panic message on stderr. Panics of
+/// other threads, such as other tests, are still reported.
+fn quietly<T>(f: impl FnOnce() -> Option<T>) -> Option<T> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                previous(info);
+            }
+        }));
+    });
+
+    let was_quiet = QUIET.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.set(was_quiet);
+    result.ok().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn shrinking() {
+        let input = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let fails = |text: &str| text.contains("3\n") && text.contains("7\n");
+        assert_eq!(shrink(input, fails), "3\n7\n");
+        assert_eq!(shrink(input, |_| true), "");
+    }
+
+    /// Sums numbers, except that it forgets the ones over 90.
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+
+        type Input = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(input: &str) -> Result<Self::Input, Error> {
+            input
+                .lines()
+                .map(|line| line.parse().map_err(|_| Error::from("Not a number")))
+                .collect()
+        }
+
+        fn part1(numbers: &Self::Input) -> Result<u32, Error> {
+            Ok(numbers.iter().filter(|&&n| n <= 90).sum())
+        }
+
+        fn part2(numbers: &Self::Input) -> Result<u32, Error> {
+            assert!(numbers.len() < 20, "Too many numbers");
+            Ok(numbers.iter().sum())
+        }
+
+        fn generate(size: usize, rng: &mut Rng) -> Result<String, Error> {
+            Ok((0..size)
+                .map(|_| format!("{}\n", rng.range(0..=99)))
+                .collect())
+        }
+    }
+
+    fn sum(input: &str) -> Option<u32> {
+        input.lines().map(|line| line.parse::<u32>().ok()).sum()
+    }
+
+    #[test]
+    fn mismatches() {
+        let mismatch = find_mismatch::<Sum, _>(1, CASES, sum).unwrap();
+        assert_eq!(mismatch.input.lines().count(), 1);
+        let number: u32 = mismatch.input.trim().parse().unwrap();
+        assert!(number > 90);
+        assert_eq!(mismatch.got, Some(Answer::Integer(0)));
+
+        // Panics are failures too, shrunk to the smallest input still panicking
+        let mismatch = find_mismatch::<Sum, _>(2, CASES, sum).unwrap();
+        assert_eq!(mismatch.input.lines().count(), 20);
+        assert_eq!(mismatch.got, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "The reference couldn't solve")]
+    fn rejected_generated_input() {
+        find_mismatch::<Sum, u32>(1, CASES, |_| None);
+    }
+}
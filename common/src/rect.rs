@@ -0,0 +1,234 @@
+use crate::pair::{Direction, Pair};
+
+/// Axis-aligned rectangle of cells, from `min` included to `min + size` excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Pair,
+    pub size: Pair,
+}
+
+impl Rect {
+    pub fn new(min: Pair, size: Pair) -> Self {
+        Self { min, size }
+    }
+
+    /// Rectangle starting at `(0, 0)`, such as the one covered by a grid.
+    pub fn from_size(size: Pair) -> Self {
+        Self::new(Pair::ZERO, size)
+    }
+
+    /// Smallest rectangle containing both corners.
+    pub fn from_corners(a: Pair, b: Pair) -> Self {
+        let min = Pair {
+            x: a.x.min(b.x),
+            y: a.y.min(b.y),
+        };
+        let max = Pair {
+            x: a.x.max(b.x),
+            y: a.y.max(b.y),
+        };
+        Self::new(min, max - min + Pair { x: 1, y: 1 })
+    }
+
+    /// Smallest rectangle containing every position, `None` when there is none.
+    pub fn bounding(positions: impl IntoIterator<Item = Pair>) -> Option<Self> {
+        let mut positions = positions.into_iter();
+        let first = positions.next()?;
+        let (min, max) = positions.fold((first, first), |(min, max), p| {
+            (
+                Pair {
+                    x: min.x.min(p.x),
+                    y: min.y.min(p.y),
+                },
+                Pair {
+                    x: max.x.max(p.x),
+                    y: max.y.max(p.y),
+                },
+            )
+        });
+        Some(Self::from_corners(min, max))
+    }
+
+    /// Bottom right cell, included in the rectangle unless it is empty.
+    pub fn max(&self) -> Pair {
+        self.min + self.size - Pair { x: 1, y: 1 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size.x <= 0 || self.size.y <= 0
+    }
+
+    pub fn area(&self) -> usize {
+        if self.is_empty() {
+            0
+        } else {
+            (self.size.x as usize) * (self.size.y as usize)
+        }
+    }
+
+    pub fn contains(&self, position: Pair) -> bool {
+        (position - self.min).within(self.size)
+    }
+
+    /// Every cell, line by line.
+    pub fn row_major(&self) -> impl Iterator<Item = Pair> {
+        let Rect { min, size } = *self;
+        (0..size.y.max(0)).flat_map(move |y| (0..size.x).map(move |x| min + Pair { x, y }))
+    }
+
+    /// Every cell, column by column.
+    pub fn column_major(&self) -> impl Iterator<Item = Pair> {
+        let Rect { min, size } = *self;
+        (0..size.x.max(0)).flat_map(move |x| (0..size.y).map(move |y| min + Pair { x, y }))
+    }
+
+    /// Cells from `start` included, in `direction`, until leaving the rectangle. No ray is longer
+    /// than the largest side, and a null `direction` only gives `start`.
+    pub fn ray(&self, start: Pair, direction: Direction) -> impl Iterator<Item = Pair> {
+        let rect = *self;
+        let steps = match direction {
+            Pair::ZERO => 1,
+            _ => self.size.x.max(self.size.y).max(0) as usize,
+        };
+        std::iter::successors(Some(start), move |&position| Some(position + direction))
+            .take_while(move |&position| rect.contains(position))
+            .take(steps)
+    }
+
+    /// Outermost cells, clockwise from `min`, each of them once.
+    pub fn border(&self) -> impl Iterator<Item = Pair> {
+        ring(self.min, self.max()).into_iter()
+    }
+
+    /// Every cell, going clockwise from `min` along the border then inwards.
+    pub fn spiral(&self) -> impl Iterator<Item = Pair> {
+        let (min, max) = (self.min, self.max());
+        (0..)
+            .map(move |depth| {
+                (
+                    min + Pair { x: depth, y: depth },
+                    max - Pair { x: depth, y: depth },
+                )
+            })
+            .take_while(|(min, max)| min.x <= max.x && min.y <= max.y)
+            .flat_map(|(min, max)| ring(min, max))
+    }
+}
+
+impl IntoIterator for Rect {
+    type Item = Pair;
+    type IntoIter = Box<dyn Iterator<Item = Pair>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.row_major())
+    }
+}
+
+/// Border of the rectangle from `min` to `max` included, clockwise.
+fn ring(min: Pair, max: Pair) -> Vec<Pair> {
+    if min.x > max.x || min.y > max.y {
+        return vec![];
+    }
+    if min.y == max.y {
+        return (min.x..=max.x).map(|x| Pair { x, y: min.y }).collect();
+    }
+    if min.x == max.x {
+        return (min.y..=max.y).map(|y| Pair { x: min.x, y }).collect();
+    }
+
+    let top = (min.x..=max.x).map(|x| Pair { x, y: min.y });
+    let right = (min.y + 1..=max.y).map(|y| Pair { x: max.x, y });
+    let bottom = (min.x..max.x).rev().map(|x| Pair { x, y: max.y });
+    let left = (min.y + 1..max.y).rev().map(|y| Pair { x: min.x, y });
+    top.chain(right).chain(bottom).chain(left).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pair::{DOWN_RIGHT, LEFT};
+
+    fn pairs<const N: usize>(coords: [(i32, i32); N]) -> Vec<Pair> {
+        coords.map(|(x, y)| Pair { x, y }).to_vec()
+    }
+
+    #[test]
+    fn bounding() {
+        let rect = Rect::bounding(pairs([(2, -1), (-3, 4), (0, 0)])).unwrap();
+        assert_eq!(rect, Rect::new(Pair { x: -3, y: -1 }, Pair { x: 6, y: 6 }));
+        assert_eq!(Rect::bounding([]), None);
+    }
+
+    #[test]
+    fn orders() {
+        let rect = Rect::new(Pair { x: 1, y: 1 }, Pair { x: 2, y: 2 });
+        assert_eq!(
+            rect.row_major().collect::<Vec<_>>(),
+            pairs([(1, 1), (2, 1), (1, 2), (2, 2)])
+        );
+        assert_eq!(
+            rect.column_major().collect::<Vec<_>>(),
+            pairs([(1, 1), (1, 2), (2, 1), (2, 2)])
+        );
+        assert_eq!(rect.area(), 4);
+        assert_eq!(
+            Rect::from_corners(Pair { x: 2, y: 2 }, Pair { x: 1, y: 1 }),
+            rect
+        );
+    }
+
+    #[test]
+    fn rays() {
+        let rect = Rect::from_size(Pair { x: 3, y: 3 });
+        assert_eq!(
+            rect.ray(Pair { x: 0, y: 0 }, DOWN_RIGHT)
+                .collect::<Vec<_>>(),
+            pairs([(0, 0), (1, 1), (2, 2)])
+        );
+        assert_eq!(rect.ray(Pair { x: 3, y: 0 }, LEFT).count(), 0);
+        assert_eq!(rect.ray(Pair { x: 1, y: 1 }, Pair::ZERO).count(), 1);
+    }
+
+    #[test]
+    fn border() {
+        let rect = Rect::from_size(Pair { x: 3, y: 3 });
+        assert_eq!(
+            rect.border().collect::<Vec<_>>(),
+            pairs([
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (1, 2),
+                (0, 2),
+                (0, 1)
+            ])
+        );
+        let line = Rect::from_size(Pair { x: 1, y: 3 });
+        assert_eq!(line.border().count(), 3);
+    }
+
+    #[test]
+    fn spiral() {
+        let rect = Rect::from_size(Pair { x: 3, y: 3 });
+        let spiral: Vec<_> = rect.spiral().collect();
+        assert_eq!(spiral.len(), 9);
+        assert_eq!(spiral.last(), Some(&Pair { x: 1, y: 1 }));
+
+        let wide = Rect::from_size(Pair { x: 4, y: 2 });
+        assert_eq!(
+            wide.spiral().collect::<Vec<_>>(),
+            pairs([
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (3, 0),
+                (3, 1),
+                (2, 1),
+                (1, 1),
+                (0, 1)
+            ])
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Pictures of grids and sets of positions, for the terminal or as PPM and PNG files.
+//!
+//! Values are turned into pixels by a [`ColorMap`], which can be any `Fn(&T) -> Rgb`.
+
+use std::{collections::HashSet, fmt::Write, fs, path::Path};
+
+use crate::{error::Error, grid::Grid, pair::Pair, rect::Rect};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    /// Blends towards `other`, `t` going from 0 (this colour) to 1 (`other`).
+    pub fn lerp(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+}
+
+/// Colour of the pixel showing a value.
+pub trait ColorMap<T: ?Sized> {
+    fn color(&self, value: &T) -> Rgb;
+}
+
+impl<T: ?Sized, F: Fn(&T) -> Rgb> ColorMap<T> for F {
+    fn color(&self, value: &T) -> Rgb {
+        self(value)
+    }
+}
+
+/// `on` for `true`, `off` for `false`.
+pub fn two_tone(on: Rgb, off: Rgb) -> impl Fn(&bool) -> Rgb {
+    move |&value| if value { on } else { off }
+}
+
+/// Linear gradient from `low` for `min` to `high` for `max`, values outside being clamped.
+pub fn gradient<T: Copy + Into<f64>>(low: Rgb, high: Rgb, min: T, max: T) -> impl Fn(&T) -> Rgb {
+    let (min, max) = (min.into(), max.into());
+    move |&value| {
+        let span = max - min;
+        let t = if span == 0.0 {
+            0.0
+        } else {
+            (value.into() - min) / span
+        };
+        low.lerp(high, t)
+    }
+}
+
+/// Rectangle of pixels, `(0, 0)` being the top left one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pixels: Grid<Rgb>,
+}
+
+impl Image {
+    pub fn from_grid<T>(grid: &Grid<T>, colors: &impl ColorMap<T>) -> Self {
+        Self {
+            pixels: grid.map(|value| colors.color(value)),
+        }
+    }
+
+    /// Pixels of the positions in `points` coloured `on`, the others `off`, within the smallest
+    /// rectangle containing them all, so that negative positions are shown as well.
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Pair>, on: Rgb, off: Rgb) -> Self {
+        let points: HashSet<Pair> = points.into_iter().copied().collect();
+        let bounds = Rect::bounding(points.iter().copied()).unwrap_or(Rect::from_size(Pair::ZERO));
+        let pixels = Grid::from_fn(bounds.size, |position| {
+            if points.contains(&(bounds.min + position)) {
+                on
+            } else {
+                off
+            }
+        });
+        Self { pixels }
+    }
+
+    pub fn size(&self) -> Pair {
+        self.pixels.size()
+    }
+
+    pub fn pixels(&self) -> &Grid<Rgb> {
+        &self.pixels
+    }
+
+    /// Enlarges every pixel to a square of `factor` pixels.
+    pub fn scale(&self, factor: i32) -> Self {
+        let factor = factor.max(1);
+        let pixels = Grid::from_fn(self.size() * factor, |position| {
+            self.pixels[position / factor]
+        });
+        Self { pixels }
+    }
+
+    /// Coloured text for terminals supporting 24-bit colours, each character showing two pixels
+    /// stacked vertically.
+    pub fn to_ansi(&self) -> String {
+        let mut text = String::new();
+        let rows: Vec<_> = self.pixels.rows().collect();
+        for pair in rows.chunks(2) {
+            for (x, top) in pair[0].iter().enumerate() {
+                let Rgb(r, g, b) = top;
+                let _ = write!(text, "\x1b[38;2;{r};{g};{b}m");
+                match pair.get(1) {
+                    Some(bottom) => {
+                        let Rgb(r, g, b) = bottom[x];
+                        let _ = write!(text, "\x1b[48;2;{r};{g};{b}m▀");
+                    }
+                    None => text.push_str("\x1b[49m▀"),
+                }
+            }
+            text.push_str("\x1b[0m\n");
+        }
+        text
+    }
+
+    /// Binary PPM (`P6`) file.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let Pair { x, y } = self.size();
+        let mut bytes = format!("P6\n{x} {y}\n255\n").into_bytes();
+        for Rgb(r, g, b) in self.pixels.iter() {
+            bytes.extend([r, g, b]);
+        }
+        bytes
+    }
+
+    /// 8-bit RGB PNG file, its pixels stored without compression.
+    pub fn to_png(&self) -> Vec<u8> {
+        let Pair { x, y } = self.size();
+
+        // Every row starts with its filter type, none here
+        let mut raw = Vec::with_capacity((y * (3 * x + 1)) as usize);
+        for row in self.pixels.rows() {
+            raw.push(0);
+            for Rgb(r, g, b) in row {
+                raw.extend([r, g, b]);
+            }
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend((x as u32).to_be_bytes());
+        header.extend((y as u32).to_be_bytes());
+        // Bit depth, RGB colour type, deflate compression, adaptive filtering, no interlace
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &header);
+        png_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    /// Writes a PNG or a PPM file, depending on the extension of `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => self.to_png(),
+            Some("ppm") => self.to_ppm(),
+            _ => return Err(format!("Unknown image format for {}", path.display()).into()),
+        };
+        fs::write(path, bytes)
+            .map_err(|e| Error::from(e).context(format!("Couldn't write {}", path.display())))
+    }
+}
+
+impl From<Grid<Rgb>> for Image {
+    fn from(pixels: Grid<Rgb>) -> Self {
+        Self { pixels }
+    }
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = u16::MAX as usize;
+
+    // 32K window, no preset dictionary, fastest compression, with the header check bits
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        let len = block.len() as u16;
+        zlib.push(is_final as u8);
+        zlib.extend(len.to_le_bytes());
+        zlib.extend((!len).to_le_bytes());
+        zlib.extend(block);
+    }
+    zlib.extend(adler32(data).to_be_bytes());
+    zlib
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn sparse_points() {
+        let trail = [Pair { x: -1, y: 0 }, Pair { x: 1, y: -1 }];
+        let image = Image::from_points(&trail, Rgb::WHITE, Rgb::BLACK);
+        assert_eq!(image.size(), Pair { x: 3, y: 2 });
+        let lit = image
+            .pixels
+            .map(|&c| if c == Rgb::WHITE { '#' } else { '.' });
+        assert_eq!(lit.to_string(), "..#\n#..");
+    }
+
+    #[test]
+    fn ppm() {
+        let grid = Grid::parse(["01"], |c| c.to_digit(10).map(|d| d == 1)).unwrap();
+        let image = Image::from_grid(&grid, &two_tone(Rgb(1, 2, 3), Rgb::BLACK));
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\0\0\0\x01\x02\x03");
+        assert_eq!(image.scale(2).size(), Pair { x: 4, y: 2 });
+    }
+
+    #[test]
+    fn png_layout() {
+        let grid = Grid::parse(["09", "90"], |c| c.to_digit(10)).unwrap();
+        let image = Image::from_grid(&grid, &gradient(Rgb::BLACK, Rgb::WHITE, 0, 9));
+        let png = image.to_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        // Two rows of a filter byte and two pixels, in a single final stored block
+        let idat = 8 + 25;
+        assert_eq!(&png[idat + 4..idat + 8], b"IDAT");
+        let zlib = &png[idat + 8..];
+        assert_eq!(&zlib[..7], [0x78, 0x01, 1, 14, 0, !14, 0xff]);
+        assert_eq!(&zlib[7..14], [0, 0, 0, 0, 255, 255, 255]);
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+
+    #[test]
+    fn ansi() {
+        let grid = Grid::parse(["1", "0", "1"], |c| c.to_digit(10).map(|d| d == 1)).unwrap();
+        let text = Image::from_grid(&grid, &two_tone(Rgb::WHITE, Rgb::BLACK)).to_ansi();
+        assert_eq!(
+            text,
+            "\x1b[38;2;255;255;255m\x1b[48;2;0;0;0m▀\x1b[0m\n\x1b[38;2;255;255;255m\x1b[49m▀\x1b[0m\n"
+        );
+    }
+}
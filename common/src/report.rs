@@ -0,0 +1,225 @@
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use crate::{answer::Answer, error::Error, solution::DynSolution};
+
+/// Outcome of one part on one input, for machine-readable output.
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: Result<Answer, ErrorReport>,
+    /// Time spent parsing the input, shared by both parts.
+    pub parse_time: Duration,
+    pub time: Duration,
+}
+
+/// Everything [`Error::diagnostic`] shows, kept as data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorReport {
+    pub message: String,
+    pub line: Option<usize>,
+    pub snippet: Option<String>,
+    pub span: Option<(usize, usize)>,
+    /// Messages of the underlying errors, outermost first.
+    pub causes: Vec<String>,
+}
+
+impl From<&Error> for ErrorReport {
+    fn from(error: &Error) -> Self {
+        let mut causes: Vec<String> = Vec::new();
+        let mut source = std::error::Error::source(error);
+        while let Some(cause) = source {
+            let message = match cause.downcast_ref::<Error>() {
+                Some(cause) => cause.message().to_string(),
+                None => cause.to_string(),
+            };
+            // Same as the diagnostic, skipping causes that only repeat the previous message
+            if causes.last().map_or(error.message(), String::as_str) != message {
+                causes.push(message);
+            }
+            source = cause.source();
+        }
+        Self {
+            message: error.message().to_string(),
+            line: error.line(),
+            snippet: error.snippet().map(str::to_string),
+            span: error.span().map(|span| (span.start, span.end)),
+            causes,
+        }
+    }
+}
+
+/// Solves both parts of `input`, read from the file named `name`, timing each step. A failure
+/// to read or parse the input is reported by both parts.
+pub fn solve(solution: &dyn DynSolution, name: &str, input: Result<String, Error>) -> [Record; 2] {
+    let record = |part, answer, parse_time, time| Record {
+        day: solution.day(),
+        part,
+        input: name.to_string(),
+        answer,
+        parse_time,
+        time,
+    };
+
+    let start = Instant::now();
+    let parsed = input.and_then(|input| solution.parse(&input));
+    let parse_time = start.elapsed();
+
+    match parsed {
+        Ok(parsed) => {
+            solution.describe(parsed.as_ref());
+            [1, 2].map(|part| {
+                let start = Instant::now();
+                let answer = match part {
+                    1 => solution.part1(parsed.as_ref()),
+                    _ => solution.part2(parsed.as_ref()),
+                };
+                let time = start.elapsed();
+                record(
+                    part,
+                    answer.map_err(|e| ErrorReport::from(&e)),
+                    parse_time,
+                    time,
+                )
+            })
+        }
+        Err(error) => {
+            let report = ErrorReport::from(&error);
+            [1, 2].map(|part| record(part, Err(report.clone()), parse_time, Duration::ZERO))
+        }
+    }
+}
+
+impl Record {
+    /// Single-line JSON object:
+    ///
+    /// ```json
+    /// {"day":10,"part":2,"input":"inputs/day10.txt","status":"ok","type":"rows",
+    ///  "answer":["##..","#..."],"parse_ns":1200,"time_ns":3400,"error":null}
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        let _ = write!(
+            json,
+            "{{\"day\":{},\"part\":{},\"input\":{},",
+            self.day,
+            self.part,
+            string(&self.input)
+        );
+        match &self.answer {
+            Ok(answer) => {
+                let value = match answer {
+                    Answer::Integer(value) => value.to_string(),
+                    Answer::Text(text) => string(text),
+                    Answer::Rows(rows) => array(rows.iter().map(|row| string(row))),
+                };
+                let _ = write!(
+                    json,
+                    "\"status\":\"ok\",\"type\":\"{}\",\"answer\":{value},",
+                    answer.kind()
+                );
+            }
+            Err(_) => json.push_str("\"status\":\"error\",\"type\":null,\"answer\":null,"),
+        }
+        let _ = write!(
+            json,
+            "\"parse_ns\":{},\"time_ns\":{},\"error\":",
+            self.parse_time.as_nanos(),
+            self.time.as_nanos()
+        );
+        match &self.answer {
+            Ok(_) => json.push_str("null"),
+            Err(report) => json.push_str(&report.to_json()),
+        }
+        json.push('}');
+        json
+    }
+}
+
+impl ErrorReport {
+    fn to_json(&self) -> String {
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "null".to_string());
+        format!(
+            "{{\"message\":{},\"line\":{},\"snippet\":{},\"span\":{},\"causes\":{}}}",
+            string(&self.message),
+            optional(self.line.map(|line| line.to_string())),
+            optional(self.snippet.as_deref().map(string)),
+            optional(self.span.map(|(start, end)| format!("[{start},{end}]"))),
+            array(self.causes.iter().map(|cause| string(cause))),
+        )
+    }
+}
+
+/// Records as a JSON array, one per line.
+pub fn to_json(records: &[Record]) -> String {
+    let lines: Vec<_> = records.iter().map(Record::to_json).collect();
+    format!("[\n{}\n]", lines.join(",\n"))
+}
+
+fn array(values: impl Iterator<Item = String>) -> String {
+    format!("[{}]", values.collect::<Vec<_>>().join(","))
+}
+
+/// JSON string literal.
+fn string(value: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(answer: Result<Answer, ErrorReport>) -> Record {
+        Record {
+            day: 10,
+            part: 2,
+            input: "in\"put".to_string(),
+            answer,
+            parse_time: Duration::from_nanos(12),
+            time: Duration::from_nanos(34),
+        }
+    }
+
+    #[test]
+    fn answer_json() {
+        let rows = Answer::Rows(vec!["#.".into(), ".#".into()]);
+        assert_eq!(
+            record(Ok(rows)).to_json(),
+            r##"{"day":10,"part":2,"input":"in\"put","status":"ok","type":"rows","answer":["#.",".#"],"parse_ns":12,"time_ns":34,"error":null}"##
+        );
+        assert!(record(Ok(Answer::Integer(-5)))
+            .to_json()
+            .contains(r#""type":"integer","answer":-5,"#));
+    }
+
+    #[test]
+    fn error_json() {
+        let error = Error::at("Unknown instruction", "jump 3", "jump")
+            .at_line(4, "jump 3")
+            .with_source(Error::new("cause\ttab"));
+        let json = record(Err(ErrorReport::from(&error))).to_json();
+        assert!(json.contains(r#""status":"error","type":null,"answer":null,"#));
+        assert!(json.ends_with(
+            r#""error":{"message":"Unknown instruction","line":4,"snippet":"jump 3","span":[0,4],"causes":["cause\ttab"]}}"#
+        ));
+    }
+}
@@ -0,0 +1,111 @@
+//! Small seeded random number generator, so that generated inputs are reproducible without
+//! depending on an external crate.
+
+use std::ops::RangeInclusive;
+
+/// SplitMix64 generator: fast, statistically sound enough for test inputs, and not meant for
+/// anything else.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value below `bound`, which must not be 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "Can't pick a value below 0");
+        // Rejects the values that would make the lowest results more likely
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    /// Uniform value within `range`, bounds included.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (low, high) = range.into_inner();
+        assert!(low <= high, "Can't pick a value in an empty range");
+        let span = high.abs_diff(low);
+        match span.checked_add(1) {
+            Some(count) => low.wrapping_add(self.below(count) as i64),
+            None => self.next_u64() as i64,
+        }
+    }
+
+    /// Uniform index into a slice of `len` items.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        // 53 random bits, as many as a float holds
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+
+    /// Random item of `items`, which must not be empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for last in (1..items.len()).rev() {
+            items.swap(last, self.index(last + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproducible() {
+        let values = |seed| {
+            let mut rng = Rng::new(seed);
+            [(); 4].map(|_| rng.next_u64())
+        };
+        assert_eq!(values(7), values(7));
+        assert_ne!(values(7), values(8));
+        // Reference values of SplitMix64 seeded with 0
+        assert_eq!(
+            values(0)[..2],
+            [0xe220_a839_7b1d_cdaf, 0x6e78_9e6a_a1b9_65f4]
+        );
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 5];
+        for _ in 0..200 {
+            let value = rng.range(-2..=2);
+            seen[(value + 2) as usize] = true;
+        }
+        assert_eq!(seen, [true; 5]);
+        assert_eq!(rng.range(3..=3), 3);
+        rng.range(i64::MIN..=i64::MAX);
+
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+        assert!(!rng.chance(0.0) && rng.chance(1.0));
+    }
+}
@@ -0,0 +1,191 @@
+//! Scanf-style parsing of a line against a pattern such as `"move {} from {} to {}"`.
+//!
+//! Every `{}` captures the text up to the literal following it, or the rest of the line for a
+//! trailing one, which is then parsed with [`FromStr`] into the matching tuple element:
+//!
+//! ```
+//! use common::scan;
+//!
+//! let (crates, from, to): (usize, usize, usize) = scan!("move {} from {} to {}", "move 1 from 2 to 3")?;
+//! assert_eq!((crates, from, to), (1, 2, 3));
+//! # Ok::<(), common::Error>(())
+//! ```
+
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// Matches `line` against `pattern` and parses the captures into `T`.
+///
+/// Errors point at the part of `line` that doesn't match, or at the capture that couldn't be
+/// parsed.
+///
+/// # Panics
+///
+/// When the number of `{}` in `pattern` differs from the arity of `T`, or when two `{}` follow
+/// each other, since there is no way to tell where the first one stops.
+#[macro_export]
+macro_rules! scan {
+    ($pattern:expr, $line:expr) => {
+        $crate::scan::scan($pattern, $line)
+    };
+    ($pattern:expr, $line:expr => $($type:ty),+ $(,)?) => {
+        $crate::scan::scan::<($($type,)+)>($pattern, $line)
+    };
+}
+
+pub fn scan<T: FromCaptures>(pattern: &str, line: &str) -> Result<T, Error> {
+    T::from_captures(line, &captures(pattern, line, T::COUNT)?)
+}
+
+/// Splits `line` into the `count` parts matching the `{}` of `pattern`.
+pub fn captures<'a>(pattern: &str, line: &'a str, count: usize) -> Result<Vec<&'a str>, Error> {
+    let literals: Vec<_> = pattern.split("{}").collect();
+    assert_eq!(
+        literals.len() - 1,
+        count,
+        "Pattern {pattern:?} doesn't have {count} captures"
+    );
+
+    let mut rest = expect_literal(line, line, literals[0])?;
+    let mut captures = Vec::with_capacity(count);
+    for (index, literal) in literals[1..].iter().enumerate() {
+        let is_last = index + 1 == count;
+        assert!(
+            is_last || !literal.is_empty(),
+            "Pattern {pattern:?} has consecutive captures"
+        );
+
+        let end = if is_last && literal.is_empty() {
+            rest.len()
+        } else if is_last {
+            // The trailing literal should end the line, so the capture may contain it as well
+            rest.rfind(literal).unwrap_or(rest.len())
+        } else {
+            rest.find(literal).unwrap_or(rest.len())
+        };
+        let (capture, after) = rest.split_at(end);
+        if capture.is_empty() {
+            return Err(Error::at("Expected a value", line, capture));
+        }
+        captures.push(capture);
+        rest = expect_literal(line, after, literal)?;
+    }
+
+    if !rest.is_empty() {
+        return Err(Error::at("Unexpected trailing text", line, rest));
+    }
+    Ok(captures)
+}
+
+/// What follows `literal` at the start of `text`, a subslice of `line`.
+fn expect_literal<'a>(line: &str, text: &'a str, literal: &str) -> Result<&'a str, Error> {
+    text.strip_prefix(literal).ok_or_else(|| {
+        // Points at the first character differing from the literal
+        let common = text
+            .char_indices()
+            .zip(literal.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(text.len().min(literal.len()), |((index, _), _)| index);
+        let found = text[common..].chars().next().map_or(0, char::len_utf8);
+        Error::at(
+            format!("Expected {literal:?}"),
+            line,
+            &text[common..common + found],
+        )
+    })
+}
+
+/// Tuple built from the captures of a pattern, one [`FromStr`] element per `{}`.
+pub trait FromCaptures: Sized {
+    const COUNT: usize;
+
+    fn from_captures(line: &str, captures: &[&str]) -> Result<Self, Error>;
+}
+
+/// Parses a single capture, the error pointing at it within `line`.
+pub fn parse<T>(line: &str, capture: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    capture.parse().map_err(|e| {
+        Error::at(
+            format!("Couldn't parse {capture:?} as {}", short_name::<T>()),
+            line,
+            capture,
+        )
+        .with_source(e)
+    })
+}
+
+/// `u32` rather than `core::primitive::u32`, `Direction` rather than `day9::Direction`.
+fn short_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+macro_rules! from_captures {
+    ($count:literal: $($type:ident $index:tt),+) => {
+        impl<$($type),+> FromCaptures for ($($type,)+)
+        where
+            $($type: FromStr, $type::Err: std::error::Error + Send + Sync + 'static,)+
+        {
+            const COUNT: usize = $count;
+
+            fn from_captures(line: &str, captures: &[&str]) -> Result<Self, Error> {
+                Ok(($(parse::<$type>(line, captures[$index])?,)+))
+            }
+        }
+    };
+}
+
+from_captures!(1: A 0);
+from_captures!(2: A 0, B 1);
+from_captures!(3: A 0, B 1, C 2);
+from_captures!(4: A 0, B 1, C 2, D 3);
+from_captures!(5: A 0, B 1, C 2, D 3, E 4);
+from_captures!(6: A 0, B 1, C 2, D 3, E 4, F 5);
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn typed_tuples() {
+        let (a, b, c, d) = scan!("{}-{},{}-{}", "2-4,6-8" => u8, u8, u8, u8).unwrap();
+        assert_eq!((a, b, c, d), (2, 4, 6, 8));
+        let (letter, steps): (char, i32) = scan!("{} {}", "R -4").unwrap();
+        assert_eq!((letter, steps), ('R', -4));
+        let (name,): (String,) = scan!("$ cd {}", "$ cd a b.txt").unwrap();
+        assert_eq!(name, "a b.txt");
+    }
+
+    #[test]
+    fn literal_errors() {
+        let error = scan!("move {} from {}", "move 1 to 2" => u8, u8).unwrap_err();
+        assert_eq!(error.message(), "Expected \" from \"");
+        assert_eq!(error.span(), Some(11..11));
+
+        let error = scan!("move {} to {}", "mave 1 to 2" => u8, u8).unwrap_err();
+        assert_eq!(error.span(), Some(1..2));
+
+        let error = scan!("{}-{}", "1-" => u8, u8).unwrap_err();
+        assert_eq!(error.message(), "Expected a value");
+        assert_eq!(error.span(), Some(2..2));
+
+        let error = scan!("[{}]", "[1] " => u8).unwrap_err();
+        assert_eq!(error.span(), Some(3..4));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = scan!("move {} from {}", "move 1 from x" => u8, u8).unwrap_err();
+        assert_eq!(error.message(), "Couldn't parse \"x\" as u8");
+        assert_eq!(error.span(), Some(12..13));
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't have 2 captures")]
+    fn arity_mismatch() {
+        let _ = scan!("{}", "1" => u8, u8);
+    }
+}
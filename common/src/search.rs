@@ -0,0 +1,399 @@
+//! Graph searches over any node type, with helpers for grids.
+//!
+//! Neighbours are given by closures, so that graphs don't need to be built beforehand. Every
+//! search returns a [`Search`], recording what was visited and how to get there.
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use crate::{grid::Grid, pair::Pair};
+
+/// Cost of a path, `Default` being zero.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// Outcome of a search: the best known cost to every visited node, and their predecessor on
+/// the path leading there.
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+    goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new() -> Self {
+        Self {
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    /// First node reached that satisfied the goal, if any.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// Cost of the path to the goal.
+    pub fn goal_cost(&self) -> Option<C> {
+        self.cost(self.goal.as_ref()?)
+    }
+
+    /// Cost of the best path found to `node`.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    pub fn is_visited(&self, node: &N) -> bool {
+        self.costs.contains_key(node)
+    }
+
+    /// Every node reached, in no particular order.
+    pub fn visited(&self) -> impl Iterator<Item = &N> {
+        self.costs.keys()
+    }
+
+    /// Nodes from a start to `node` included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.is_visited(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last()?) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Nodes from a start to the goal included.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+}
+
+/// Breadth-first search from `starts`, every edge costing 1, stopping at the first node
+/// satisfying `is_goal`.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if search.costs.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        let cost = search.costs[&node] + 1;
+        for next in neighbors(&node) {
+            if let Entry::Vacant(entry) = search.costs.entry(next.clone()) {
+                entry.insert(cost);
+                search.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// Every node reachable from `starts`.
+pub fn flood_fill<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs(starts, neighbors, |_| false)
+        .costs
+        .into_keys()
+        .collect()
+}
+
+/// Cheapest paths from `starts`, `neighbors` giving every node reachable in one step along
+/// with its cost, stopping at the first node satisfying `is_goal`.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbors, |_| C::default(), is_goal)
+}
+
+/// [`dijkstra`] guided by `heuristic`, which must never overestimate the cost to the goal.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        search.costs.insert(start.clone(), C::default());
+        queue.push(Reverse(Queued {
+            priority: heuristic(&start),
+            cost: C::default(),
+            node: start,
+        }));
+    }
+
+    while let Some(Reverse(Queued { cost, node, .. })) = queue.pop() {
+        if search.costs.get(&node).is_some_and(|&best| best < cost) {
+            // Already reached more cheaply since it was queued
+            continue;
+        }
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        for (next, step) in neighbors(&node) {
+            let cost = cost + step;
+            if search.costs.get(&next).is_some_and(|&best| best <= cost) {
+                continue;
+            }
+            search.costs.insert(next.clone(), cost);
+            search.parents.insert(next.clone(), node.clone());
+            queue.push(Reverse(Queued {
+                priority: cost + heuristic(&next),
+                cost,
+                node: next,
+            }));
+        }
+    }
+    search
+}
+
+/// Node waiting in the priority queue, ordered by priority only.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+/// Which cells are next to each other on a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Orthogonal neighbours only.
+    Four,
+    /// Diagonal neighbours as well.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn neighbors(self, position: Pair) -> Vec<Pair> {
+        match self {
+            Connectivity::Four => position.neighbors4().to_vec(),
+            Connectivity::Eight => position.neighbors8().to_vec(),
+        }
+    }
+
+    /// Fewest steps between two positions, when nothing is in the way.
+    pub fn distance(self, from: Pair, to: Pair) -> i32 {
+        match self {
+            Connectivity::Four => (to - from).manhattan(),
+            Connectivity::Eight => (to - from).chebyshev(),
+        }
+    }
+}
+
+/// Neighbours of `position` within `grid`, when `can_move` allows stepping there.
+fn grid_steps<'a, T>(
+    grid: &'a Grid<T>,
+    connectivity: Connectivity,
+    position: Pair,
+    can_move: &'a mut impl FnMut(&T, &T) -> bool,
+) -> impl Iterator<Item = Pair> + 'a {
+    let from = &grid[position];
+    connectivity
+        .neighbors(position)
+        .into_iter()
+        .filter(move |&next| grid.get(next).is_some_and(|to| can_move(from, to)))
+}
+
+/// [`bfs`] on a grid, moving between neighbouring cells when `can_move(from, to)` allows it.
+pub fn grid_bfs<T>(
+    grid: &Grid<T>,
+    start: Pair,
+    connectivity: Connectivity,
+    mut can_move: impl FnMut(&T, &T) -> bool,
+    is_goal: impl FnMut(&Pair) -> bool,
+) -> Search<Pair, usize> {
+    bfs(
+        [start],
+        |&position| grid_steps(grid, connectivity, position, &mut can_move).collect::<Vec<_>>(),
+        is_goal,
+    )
+}
+
+/// Cells connected to `start` through neighbours for which `can_move(from, to)` holds, such as
+/// a region of equal cells.
+pub fn grid_flood_fill<T>(
+    grid: &Grid<T>,
+    start: Pair,
+    connectivity: Connectivity,
+    mut can_move: impl FnMut(&T, &T) -> bool,
+) -> HashSet<Pair> {
+    flood_fill([start], |&position| {
+        grid_steps(grid, connectivity, position, &mut can_move).collect::<Vec<_>>()
+    })
+}
+
+/// [`astar`] on a grid from `start` to `goal`, `cost(from, to)` giving the cost of stepping
+/// between neighbouring cells, or `None` when that's not possible. Every step should cost at
+/// least 1, the distance to the goal being the heuristic.
+pub fn grid_astar<T>(
+    grid: &Grid<T>,
+    start: Pair,
+    goal: Pair,
+    connectivity: Connectivity,
+    mut cost: impl FnMut(&T, &T) -> Option<i32>,
+) -> Search<Pair, i32> {
+    astar(
+        [start],
+        |&position| {
+            let from = &grid[position];
+            connectivity
+                .neighbors(position)
+                .into_iter()
+                .filter_map(|next| Some((next, cost(from, grid.get(next)?)?)))
+                .collect::<Vec<_>>()
+        },
+        |&position| connectivity.distance(position, goal),
+        |&position| position == goal,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#E..
+.##...#.";
+
+    fn maze() -> (Grid<char>, Pair, Pair) {
+        let grid = Grid::parse(MAZE.lines(), Some).unwrap();
+        let find = |c| grid.cells().find(|&(_, &cell)| cell == c).unwrap().0;
+        let (start, end) = (find('S'), find('E'));
+        (grid, start, end)
+    }
+
+    fn is_open(_: &char, to: &char) -> bool {
+        *to != '#'
+    }
+
+    #[test]
+    fn grid_shortest_path() {
+        let (grid, start, end) = maze();
+        let search = grid_bfs(&grid, start, Connectivity::Four, is_open, |&p| p == end);
+        assert_eq!(search.goal_cost(), Some(9));
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 10);
+        assert_eq!((path[0], path[9]), (start, end));
+        assert!(path
+            .windows(2)
+            .all(|step| (step[1] - step[0]).manhattan() == 1));
+
+        let diagonal = grid_bfs(&grid, start, Connectivity::Eight, is_open, |&p| p == end);
+        assert_eq!(diagonal.goal_cost(), Some(6));
+    }
+
+    #[test]
+    fn weighted_paths() {
+        let (grid, start, end) = maze();
+        // Every step costs 2, except the last one onto the exit
+        let cost = |_: &char, to: &char| (*to != '#').then_some(if *to == 'E' { 1 } else { 2 });
+        let astar = grid_astar(&grid, start, end, Connectivity::Four, cost);
+        assert_eq!(astar.goal_cost(), Some(17));
+
+        let dijkstra = dijkstra(
+            [start],
+            |&p| {
+                let from = &grid[p];
+                p.neighbors4()
+                    .into_iter()
+                    .filter_map(|n| Some((n, cost(from, grid.get(n)?)?)))
+                    .collect::<Vec<_>>()
+            },
+            |&p| p == end,
+        );
+        assert_eq!(dijkstra.goal_cost(), astar.goal_cost());
+        assert_eq!(dijkstra.path().map(|p| p.len()), Some(10));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let search = bfs(
+            [0],
+            |&n: &i32| [n + 2, n - 2].into_iter().filter(|n| n.abs() < 10),
+            |&n| n == 3,
+        );
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.visited().count(), 9);
+        assert_eq!(search.path_to(&-4), Some(vec![0, -2, -4]));
+        assert_eq!(search.path_to(&3), None);
+    }
+
+    #[test]
+    fn regions() {
+        let (grid, start, _) = maze();
+        let region = grid_flood_fill(&grid, start, Connectivity::Four, is_open);
+        assert_eq!(region.len(), grid.iter().filter(|&&c| c != '#').count());
+        let walls = grid_flood_fill(&grid, Pair { x: 2, y: 0 }, Connectivity::Four, |a, b| {
+            a == b
+        });
+        assert_eq!(walls.len(), 3);
+    }
+}
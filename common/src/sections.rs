@@ -0,0 +1,149 @@
+//! Inputs made of sections or records separated by blank lines.
+
+use std::{
+    iter::{Peekable, Zip},
+    ops::RangeFrom,
+    str::Lines,
+};
+
+use crate::error::{Context, Error};
+
+/// Run of non-blank lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    first_line: usize,
+    lines: Vec<&'a str>,
+}
+
+impl<'a> Section<'a> {
+    /// 1-based number of the first line within the whole input.
+    pub fn first_line(&self) -> usize {
+        self.first_line
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = &'a str> + '_ {
+        self.lines.iter().copied()
+    }
+
+    /// Lines along with their 1-based number within the whole input.
+    pub fn numbered_lines(&self) -> impl DoubleEndedIterator<Item = (usize, &'a str)> + '_ {
+        let first_line = self.first_line;
+        self.lines
+            .iter()
+            .copied()
+            .enumerate()
+            .map(move |(index, line)| (first_line + index, line))
+    }
+
+    /// Parses every line with `parse`, errors getting the line they come from.
+    pub fn parse_lines<T>(
+        &self,
+        mut parse: impl FnMut(&'a str) -> Result<T, Error>,
+    ) -> Result<Vec<T>, Error> {
+        self.numbered_lines()
+            .map(|(number, line)| parse(line).at_line(number, line))
+            .collect()
+    }
+}
+
+/// Splits an input on blank lines, ignoring those before the first section and after the last
+/// one, and treating several blank lines in a row as a single separator.
+pub struct Sections<'a> {
+    lines: Peekable<Zip<RangeFrom<usize>, Lines<'a>>>,
+}
+
+impl<'a> Sections<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            lines: (1..).zip(input.lines()).peekable(),
+        }
+    }
+
+    /// Next section, named `what` in the error when there is none left.
+    pub fn expect(&mut self, what: &str) -> Result<Section<'a>, Error> {
+        self.next()
+            .ok_or_else(|| Error::new(format!("Missing {what}")))
+    }
+
+    /// Checks that every section has been read.
+    pub fn end(mut self) -> Result<(), Error> {
+        match self.next() {
+            None => Ok(()),
+            Some(section) => {
+                let line = section.lines[0];
+                Err(Error::new("Unexpected section").at_line(section.first_line, line))
+            }
+        }
+    }
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.lines.next_if(|(_, line)| is_blank(line)).is_some() {}
+
+        let &(first_line, _) = self.lines.peek()?;
+        let mut lines = Vec::new();
+        while let Some((_, line)) = self.lines.next_if(|(_, line)| !is_blank(line)) {
+            lines.push(line);
+        }
+        Some(Section { first_line, lines })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_lines() {
+        let input = "\n\na\nb\n\n\n \nc\n\n";
+        let sections: Vec<_> = Sections::new(input).collect();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].first_line(), 3);
+        assert_eq!(sections[0].lines().collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(sections[1].numbered_lines().collect::<Vec<_>>(), [(8, "c")]);
+    }
+
+    #[test]
+    fn crlf_without_trailing_newline() {
+        let lines: Vec<Vec<_>> = Sections::new("1\r\n2\r\n\r\n3")
+            .map(|section| section.lines().collect())
+            .collect();
+        assert_eq!(lines, [vec!["1", "2"], vec!["3"]]);
+    }
+
+    #[test]
+    fn typed_sections() {
+        let mut input = Sections::new("1\n2\n\n3\nx\n\n4");
+        let first = input.expect("first").unwrap();
+        assert_eq!(first.parse_lines(|line| Ok(line.len())).unwrap(), [1, 1]);
+
+        let error = input
+            .expect("second")
+            .unwrap()
+            .parse_lines(|line| {
+                line.parse::<u8>()
+                    .map_err(|e| Error::at("Not a number", line, line).with_source(e))
+            })
+            .unwrap_err();
+        assert_eq!(error.line(), Some(5));
+
+        assert_eq!(Sections::new("1\n\n2").end().unwrap_err().line(), Some(1));
+        input.expect("third").unwrap();
+        assert!(input.expect("fourth").is_err());
+    }
+}
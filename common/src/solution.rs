@@ -0,0 +1,136 @@
+use std::{any::Any, env, process::ExitCode};
+
+use crate::{
+    answer::Answer,
+    error::Error,
+    frames::Recorder,
+    input::Input,
+    log::{self, Level},
+    rng::Rng,
+};
+
+/// A day of the calendar: the input is parsed once, then shared by both parts.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Answer1: Into<Answer>;
+    type Answer2: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Error>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Error>;
+
+    /// Logs details of a parsed input. The runner calls it once per input, outside of any timing,
+    /// rather than on every parse.
+    fn describe(_input: &Self::Input) {}
+
+    /// Replays `part` step by step into `recorder`, for the days that are simulations.
+    fn record(_input: &Self::Input, _part: u8, _recorder: &mut Recorder) -> Result<(), Error> {
+        Err(format!("Day {} can't be recorded", Self::DAY).into())
+    }
+
+    /// Random valid input, `size` being about how many lines or records it has.
+    fn generate(_size: usize, _rng: &mut Rng) -> Result<String, Error> {
+        Err(format!("Day {} has no input generator", Self::DAY).into())
+    }
+}
+
+/// Object-safe counterpart of [`Solution`], so that every day can be listed and run the same way.
+pub trait DynSolution {
+    fn day(&self) -> u8;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, Error>;
+    fn part1(&self, input: &dyn Any) -> Result<Answer, Error>;
+    fn part2(&self, input: &dyn Any) -> Result<Answer, Error>;
+    fn describe(&self, input: &dyn Any);
+    fn record(&self, input: &dyn Any, part: u8, recorder: &mut Recorder) -> Result<(), Error>;
+    fn generate(&self, size: usize, rng: &mut Rng) -> Result<String, Error>;
+
+    fn solve(&self, input: &str) -> Result<[Answer; 2], Error> {
+        let input = self.parse(input)?;
+        self.describe(input.as_ref());
+        Ok([self.part1(input.as_ref())?, self.part2(input.as_ref())?])
+    }
+}
+
+impl<S: Solution> DynSolution for S
+where
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, Error> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn part1(&self, input: &dyn Any) -> Result<Answer, Error> {
+        Ok(S::part1(downcast::<S>(input))?.into())
+    }
+
+    fn part2(&self, input: &dyn Any) -> Result<Answer, Error> {
+        Ok(S::part2(downcast::<S>(input))?.into())
+    }
+
+    fn describe(&self, input: &dyn Any) {
+        S::describe(downcast::<S>(input))
+    }
+
+    fn record(&self, input: &dyn Any, part: u8, recorder: &mut Recorder) -> Result<(), Error> {
+        S::record(downcast::<S>(input), part, recorder)
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Result<String, Error> {
+        S::generate(size, rng)
+    }
+}
+
+fn downcast<S: Solution>(input: &dyn Any) -> &S::Input
+where
+    S::Input: 'static,
+{
+    input
+        .downcast_ref()
+        .expect("Input should have been parsed by the same day")
+}
+
+/// Reads the whole puzzle input from `path`, or from stdin when no path is given or it is `-`.
+pub fn read_input(path: Option<&str>) -> Result<String, Error> {
+    Input::open(path.unwrap_or("-"))?.read_to_string()
+}
+
+/// Solves `input` and prints both answers, multi-line ones starting on their own line.
+pub fn print_answers(solution: &dyn DynSolution, input: &str) -> Result<(), Error> {
+    for (part, answer) in (1..).zip(solution.solve(input)?) {
+        match answer {
+            Answer::Rows(_) => println!("Part {part}:\n{answer}"),
+            _ => println!("Part {part}: {answer}"),
+        }
+    }
+    Ok(())
+}
+
+/// Entry point of the per-day binaries: `dayN [-q|-v|-vv] [input]`.
+pub fn main(solution: &dyn DynSolution) -> ExitCode {
+    let mut path = None;
+    let mut verbosity: i8 = 0;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "-v" => verbosity = verbosity.saturating_add(1),
+            "-vv" => verbosity = verbosity.saturating_add(2),
+            "-q" => verbosity = verbosity.saturating_sub(1),
+            _ => path = Some(arg),
+        }
+    }
+    log::set_max_level(Level::from_verbosity(verbosity));
+
+    let result = read_input(path.as_deref()).and_then(|input| print_answers(solution, &input));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprint!("{}", error.diagnostic());
+            ExitCode::FAILURE
+        }
+    }
+}
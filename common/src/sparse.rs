@@ -0,0 +1,257 @@
+//! Unbounded 2D grid for cells scattered over an area that isn't known beforehand.
+
+use std::collections::HashMap;
+
+use crate::{grid::Grid, pair::Pair, rect::Rect};
+
+/// Side of the square chunks cells are stored in.
+const CHUNK: i32 = 16;
+
+/// Cells keyed by position, negative ones included, stored by chunks of `16x16` so that dense
+/// regions cost little more than a [`Grid`] while empty ones cost nothing.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    chunks: HashMap<Pair, Chunk<T>>,
+    len: usize,
+    bounds: Option<Rect>,
+}
+
+#[derive(Debug, Clone)]
+struct Chunk<T> {
+    cells: Box<[Option<T>]>,
+    len: usize,
+}
+
+impl<T> Chunk<T> {
+    fn new() -> Self {
+        Self {
+            cells: (0..CHUNK * CHUNK).map(|_| None).collect(),
+            len: 0,
+        }
+    }
+}
+
+/// Chunk containing `position`, and the index of the cell within it.
+fn locate(position: Pair) -> (Pair, usize) {
+    let chunk = Pair {
+        x: position.x.div_euclid(CHUNK),
+        y: position.y.div_euclid(CHUNK),
+    };
+    let offset = position - chunk * CHUNK;
+    (chunk, (offset.y * CHUNK + offset.x) as usize)
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            chunks: HashMap::new(),
+            len: 0,
+            bounds: None,
+        }
+    }
+
+    /// Number of cells holding a value.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Smallest rectangle containing every cell, `None` when there is none.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+    }
+
+    pub fn contains(&self, position: Pair) -> bool {
+        self.get(position).is_some()
+    }
+
+    pub fn get(&self, position: Pair) -> Option<&T> {
+        let (chunk, index) = locate(position);
+        self.chunks.get(&chunk)?.cells[index].as_ref()
+    }
+
+    pub fn get_mut(&mut self, position: Pair) -> Option<&mut T> {
+        let (chunk, index) = locate(position);
+        self.chunks.get_mut(&chunk)?.cells[index].as_mut()
+    }
+
+    /// Sets the cell at `position`, returning its previous value.
+    pub fn insert(&mut self, position: Pair, value: T) -> Option<T> {
+        let previous = self.cell(position).replace(value);
+        if previous.is_none() {
+            self.added(position);
+        }
+        previous
+    }
+
+    /// Value of the cell at `position`, set by `default` when there is none.
+    pub fn get_or_insert_with(&mut self, position: Pair, default: impl FnOnce() -> T) -> &mut T {
+        if !self.contains(position) {
+            self.insert(position, default());
+        }
+        self.cell(position).as_mut().expect("The cell was just set")
+    }
+
+    pub fn remove(&mut self, position: Pair) -> Option<T> {
+        let (key, index) = locate(position);
+        let chunk = self.chunks.get_mut(&key)?;
+        let value = chunk.cells[index].take()?;
+
+        chunk.len -= 1;
+        if chunk.len == 0 {
+            self.chunks.remove(&key);
+        }
+        self.len -= 1;
+        if let Some(bounds) = self.bounds {
+            let (min, max) = (bounds.min, bounds.max());
+            if [min.x, max.x].contains(&position.x) || [min.y, max.y].contains(&position.y) {
+                self.bounds = Rect::bounding(self.positions());
+            }
+        }
+        Some(value)
+    }
+
+    /// Cells along with their position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pair, &T)> {
+        let mut chunks: Vec<(Pair, &Chunk<T>)> = self
+            .chunks
+            .iter()
+            .map(|(&key, chunk)| (key, chunk))
+            .collect();
+        chunks.sort_unstable_by_key(|(key, _)| (key.y, key.x));
+
+        let rows: Vec<Vec<_>> = chunks
+            .chunk_by(|(a, _), (b, _)| a.y == b.y)
+            .map(<[_]>::to_vec)
+            .collect();
+        rows.into_iter().flat_map(|row| {
+            (0..CHUNK).flat_map(move |y| {
+                row.clone().into_iter().flat_map(move |(key, chunk)| {
+                    (0..CHUNK).filter_map(move |x| {
+                        let value = chunk.cells[(y * CHUNK + x) as usize].as_ref()?;
+                        Some((key * CHUNK + Pair { x, y }, value))
+                    })
+                })
+            })
+        })
+    }
+
+    /// Positions of the cells, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pair> + '_ {
+        self.iter().map(|(position, _)| position)
+    }
+
+    /// Dense copy of the cells within [`bounds`](Self::bounds), `empty` filling the others. Cell
+    /// `(0, 0)` of the grid is the top left corner of the bounds.
+    pub fn to_grid(&self, empty: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let bounds = self.bounds.unwrap_or(Rect::from_size(Pair::ZERO));
+        Grid::from_fn(bounds.size, |position| {
+            self.get(bounds.min + position)
+                .cloned()
+                .unwrap_or_else(|| empty.clone())
+        })
+    }
+
+    fn cell(&mut self, position: Pair) -> &mut Option<T> {
+        let (chunk, index) = locate(position);
+        &mut self.chunks.entry(chunk).or_insert_with(Chunk::new).cells[index]
+    }
+
+    fn added(&mut self, position: Pair) {
+        let (chunk, _) = locate(position);
+        self.chunks
+            .get_mut(&chunk)
+            .expect("The chunk was just made")
+            .len += 1;
+        self.len += 1;
+        self.bounds = Some(match self.bounds {
+            None => Rect::new(position, Pair { x: 1, y: 1 }),
+            Some(bounds) => Rect::bounding([bounds.min, bounds.max(), position])
+                .expect("There are three positions"),
+        });
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(Pair, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pair, T)>>(cells: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(cells);
+        grid
+    }
+}
+
+impl<T> Extend<(Pair, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Pair, T)>>(&mut self, cells: I) {
+        for (position, value) in cells {
+            self.insert(position, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(x: i32, y: i32) -> Pair {
+        Pair { x, y }
+    }
+
+    #[test]
+    fn cells_across_chunks() {
+        let mut grid: SparseGrid<char> = [(at(20, -1), 'b'), (at(-3, -1), 'a'), (at(0, 40), 'c')]
+            .into_iter()
+            .collect();
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.get(at(-3, -1)), Some(&'a'));
+        assert_eq!(grid.get(at(-3, 0)), None);
+        assert_eq!(grid.insert(at(20, -1), 'B'), Some('b'));
+        assert_eq!(grid.len(), 3);
+
+        let cells: Vec<_> = grid.iter().collect();
+        assert_eq!(
+            cells,
+            [(at(-3, -1), &'a'), (at(20, -1), &'B'), (at(0, 40), &'c')]
+        );
+
+        *grid.get_or_insert_with(at(1, 1), || 'x') = 'd';
+        *grid.get_or_insert_with(at(1, 1), || 'x') = 'e';
+        assert_eq!(grid.get(at(1, 1)), Some(&'e'));
+    }
+
+    #[test]
+    fn bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(at(2, -1), ());
+        grid.insert(at(-3, 4), ());
+        grid.insert(at(0, 0), ());
+        assert_eq!(grid.bounds(), Some(Rect::new(at(-3, -1), at(6, 6))));
+
+        assert_eq!(grid.remove(at(-3, 4)), Some(()));
+        assert_eq!(grid.remove(at(-3, 4)), None);
+        assert_eq!(grid.bounds(), Some(Rect::new(at(0, -1), at(3, 2))));
+        grid.remove(at(2, -1));
+        grid.remove(at(0, 0));
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn to_grid() {
+        let grid: SparseGrid<char> = [(at(-1, 0), '#'), (at(1, -1), '#')].into_iter().collect();
+        assert_eq!(grid.to_grid('.').to_string(), "..#\n#..");
+        assert_eq!(SparseGrid::new().to_grid('.').size(), Pair::ZERO);
+    }
+}
@@ -0,0 +1,17 @@
+use std::fmt::Write;
+
+use common::rng::Rng;
+
+/// Inventories of `size` elves, at least 3 of them, each carrying a few snacks.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for elf in 0..size.max(3) {
+        if elf > 0 {
+            input.push('\n');
+        }
+        for _ in 0..rng.range(1..=6) {
+            writeln!(input, "{}", rng.range(1000..=60000)).unwrap();
+        }
+    }
+    input
+}
@@ -1,42 +1,69 @@
-use std::borrow::Borrow;
-
-pub fn parse_elves<I>(lines: I) -> Vec<u32>
-where
-    I: IntoIterator,
-    I::Item: Borrow<str>,
-{
-    let mut elves: Vec<u32> = vec![];
-    let mut accumulator: u32 = 0;
-
-    for line in lines {
-        match line.borrow().parse::<u32>() {
-            Ok(number) => accumulator += number,
-            Err(_) => {
-                elves.push(accumulator);
-                accumulator = 0
-            }
-        }
+use common::{rng::Rng, sections::Sections, Error, Solution};
+
+mod generator;
+#[cfg(test)]
+mod reference;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_elves(input)
     }
 
-    if accumulator != 0 {
-        elves.push(accumulator)
+    fn part1(elves: &Self::Input) -> Result<u32, Error> {
+        solve_part1(elves)
     }
 
-    elves
+    fn part2(elves: &Self::Input) -> Result<u32, Error> {
+        solve_part2(elves)
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Result<String, Error> {
+        Ok(generator::generate(size, rng))
+    }
+}
+
+/// Total calories carried by each elf, elves being separated by blank lines.
+pub fn parse_elves(input: &str) -> Result<Vec<u32>, Error> {
+    Sections::new(input)
+        .map(|elf| {
+            let calories = elf.parse_lines(|line| {
+                line.parse::<u32>()
+                    .map_err(|e| Error::at("Couldn't parse calories", line, line).with_source(e))
+            })?;
+            calories
+                .iter()
+                .try_fold(0u32, |total, &snack| total.checked_add(snack))
+                .ok_or_else(|| {
+                    let first = elf.lines().next().unwrap_or_default();
+                    Error::new("Too many calories to count").at_line(elf.first_line(), first)
+                })
+        })
+        .collect()
 }
 
-pub fn solve_part1(elves: &Vec<u32>) -> Result<u32, &'static str> {
+pub fn solve_part1(elves: &[u32]) -> Result<u32, Error> {
     elves
         .iter()
         .max()
-        .map(u32::clone)
-        .ok_or("Max couldn't be found")
+        .copied()
+        .ok_or_else(|| "Max couldn't be found".into())
 }
 
-pub fn solve_part2(elves: &Vec<u32>) -> u32 {
-    let mut elves = elves.clone();
+pub fn solve_part2(elves: &[u32]) -> Result<u32, Error> {
+    let mut elves = elves.to_vec();
     elves.sort_unstable_by(|a, b| b.cmp(a));
-    elves[..3].iter().sum()
+    let top = elves.get(..3).ok_or("Fewer than 3 elves")?;
+    top.iter()
+        .try_fold(0u32, |total, &elf| total.checked_add(elf))
+        .ok_or_else(|| "Too many calories to count".into())
 }
 
 #[cfg(test)]
@@ -61,10 +88,17 @@ mod test {
 
     #[test]
     fn parser() {
-        let elves = parse_elves(INPUT.lines());
+        let elves = parse_elves(INPUT).unwrap();
         assert_eq!(elves, vec![6000, 4000, 11000, 24000, 10000]);
     }
 
+    #[test]
+    fn invalid_line() {
+        let error = parse_elves(&INPUT.replace("5000", "5k")).unwrap_err();
+        assert_eq!(error.line(), Some(7));
+        assert_eq!(error.snippet(), Some("5k"));
+    }
+
     #[test]
     fn solution_part1() {
         let elves = vec![6000, 4000, 11000, 24000, 10000];
@@ -75,7 +109,8 @@ mod test {
     #[test]
     fn solution_part2() {
         let elves = vec![6000, 4000, 11000, 24000, 10000];
-        let result = super::solve_part2(&elves);
+        let result = super::solve_part2(&elves).expect("Couldn't solve");
         assert_eq!(result, 45000);
+        assert!(super::solve_part2(&elves[..2]).is_err());
     }
 }
@@ -0,0 +1,43 @@
+//! Naive solutions, checked against the real ones on generated inputs.
+
+use common::property::assert_matches_reference;
+
+use crate::Day1;
+
+/// Calories of each elf, summed line by line.
+fn elves(input: &str) -> Option<Vec<u32>> {
+    let mut elves = vec![0];
+    for line in input.lines() {
+        if line.is_empty() {
+            elves.push(0);
+        } else {
+            *elves.last_mut()? += line.parse::<u32>().ok()?;
+        }
+    }
+    Some(elves)
+}
+
+fn part1(input: &str) -> Option<u32> {
+    elves(input)?.into_iter().max()
+}
+
+/// Takes the largest elf out three times.
+fn part2(input: &str) -> Option<u32> {
+    let mut elves = elves(input)?;
+    let mut total = 0;
+    for _ in 0..3 {
+        let largest = (0..elves.len()).max_by_key(|&index| elves[index])?;
+        total += elves.remove(largest);
+    }
+    Some(total)
+}
+
+#[test]
+fn part1_matches_reference() {
+    assert_matches_reference::<Day1, _>(1, part1);
+}
+
+#[test]
+fn part2_matches_reference() {
+    assert_matches_reference::<Day1, _>(2, part2);
+}
@@ -0,0 +1,24 @@
+use std::fmt::Write;
+
+use common::rng::Rng;
+
+/// Program of `size` instructions, keeping the register within the width of the screen.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    let mut register = 1;
+    for _ in 0..size {
+        if rng.chance(0.4) {
+            input.push_str("noop\n");
+            continue;
+        }
+        let target = loop {
+            let target = rng.range(0..=39);
+            if target != register {
+                break target;
+            }
+        };
+        writeln!(input, "addx {}", target - register).unwrap();
+        register = target;
+    }
+    input
+}
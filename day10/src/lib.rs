@@ -1,57 +1,114 @@
-use common::lines_from_file;
+use common::{
+    error::Context,
+    frames::{Frame, Recorder},
+    pair::Pair,
+    render::{Image, Rgb},
+    rng::Rng,
+    scan, trace, Error, Grid, Solution,
+};
 use std::borrow::Borrow;
 
-type Error = &'static str;
-
-pub fn run(filename: &str) -> Result<(), Error> {
-    let steps = lines_from_file(filename)?
-        .map(Instruction::parse)
-        .collect::<Result<Vec<_>, _>>()?;
-    let solution_p1 = solve_p1(steps.iter());
-    println!("P1 Solution: {}", solution_p1);
-    println!("P2 Solution:");
-    solve_p2(steps.iter());
-    Ok(())
+mod generator;
+#[cfg(test)]
+mod reference;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<Instruction>;
+    type Answer1 = i32;
+    type Answer2 = Grid<char>;
+
+    /// Also runs the program, so that the register can't overflow later on.
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let mut register: i32 = 1;
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let instruction = Instruction::parse(line).at_line(index + 1, line)?;
+                if let Instruction::Addx(value) = instruction {
+                    register = register.checked_add(value).ok_or_else(|| {
+                        Error::at("The register overflows", line, line).at_line(index + 1, line)
+                    })?;
+                }
+                Ok(instruction)
+            })
+            .collect()
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<i32, Error> {
+        solve_p1(instructions.iter()).ok_or_else(|| "The signal strength overflows".into())
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<Grid<char>, Error> {
+        Ok(solve_p2(instructions.iter()))
+    }
+
+    /// Both parts replay the same cycles, the first one also showing the signal strength.
+    fn record(instructions: &Self::Input, part: u8, recorder: &mut Recorder) -> Result<(), Error> {
+        if !(1..=2).contains(&part) {
+            return Err(format!("Day {} has no part {part}", Self::DAY).into());
+        }
+        let mut strength: i32 = 0;
+        run_crt(instructions.iter(), |cycle, register, screen| {
+            if cycle % 40 == 20 {
+                strength = strength.saturating_add(cycle.saturating_mul(register));
+            }
+            recorder.push(|| {
+                let mut caption = format!("Cycle {cycle}: X = {register}");
+                if part == 1 {
+                    caption += &format!(", signal strength {strength}");
+                }
+                Frame::new(draw_screen(screen, cycle, register), caption)
+            });
+        });
+        Ok(())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Result<String, Error> {
+        Ok(generator::generate(size, rng))
+    }
 }
 
-enum Instruction {
-    ADDX(i32),
-    NOOP,
+#[derive(Debug)]
+pub enum Instruction {
+    Addx(i32),
+    Noop,
 }
 
 impl Instruction {
     fn parse(line: impl Borrow<str>) -> Result<Self, Error> {
-        let mut terms = line.borrow().split_whitespace();
-        let instruction = terms.next().ok_or("Missing instruction")?;
-        match instruction {
-            "addx" => {
-                let operand = terms
-                    .next()
-                    .ok_or("Missing addx operand")?
-                    .parse()
-                    .or(Err("Couldn't parse addx operand"))?;
-                Ok(Self::ADDX(operand))
-            }
-            "noop" => Ok(Self::NOOP),
-            _ => Err("Unknown instruction"),
+        let line = line.borrow();
+        if line == "noop" {
+            Ok(Self::Noop)
+        } else if line.starts_with("addx") {
+            let (operand,) = scan!("addx {}", line => i32)?;
+            Ok(Self::Addx(operand))
+        } else {
+            let instruction = line.split(' ').next().unwrap_or(line);
+            Err(Error::at("Unknown instruction", line, instruction))
         }
     }
-    fn get_duration(self: &Self) -> i32 {
+    fn get_duration(&self) -> i32 {
         match self {
-            Instruction::ADDX(_) => 2,
-            Instruction::NOOP => 1,
+            Instruction::Addx(_) => 2,
+            Instruction::Noop => 1,
         }
     }
-    fn execute(self: &Self, register: &mut i32) {
+    fn execute(&self, register: &mut i32) {
         match self {
-            Instruction::ADDX(x) => *register += x,
-            Instruction::NOOP => {}
+            Instruction::Addx(x) => *register += x,
+            Instruction::Noop => {}
         };
     }
 }
 
-fn solve_p1(instructions: impl Iterator<Item = impl Borrow<Instruction>>) -> i32 {
-    let mut sum = 0;
+/// Sum of the signal strengths, `None` when it doesn't fit.
+fn solve_p1(instructions: impl Iterator<Item = impl Borrow<Instruction>>) -> Option<i32> {
+    let mut sum: i32 = 0;
     let mut cycle: i32 = 0;
     let mut register: i32 = 1;
 
@@ -60,34 +117,68 @@ fn solve_p1(instructions: impl Iterator<Item = impl Borrow<Instruction>>) -> i32
 
         for _ in 0..instruction.get_duration() {
             cycle += 1;
+            trace!("Cycle {cycle}: X = {register}");
             if cycle % 40 == 20 {
-                sum += cycle * register;
+                sum = cycle
+                    .checked_mul(register)
+                    .and_then(|strength| sum.checked_add(strength))?;
             }
         }
 
         instruction.execute(&mut register);
     }
 
-    sum
+    Some(sum)
 }
 
-fn solve_p2(instructions: impl Iterator<Item = impl Borrow<Instruction>>) -> () {
+const SCREEN_SIZE: Pair = Pair { x: 40, y: 6 };
+
+fn solve_p2(instructions: impl Iterator<Item = impl Borrow<Instruction>>) -> Grid<char> {
+    run_crt(instructions, |_, _, _| {})
+}
+
+/// Draws the screen, calling `on_cycle` with the number of the cycle, the register during it
+/// and the screen once it is drawn.
+fn run_crt(
+    instructions: impl Iterator<Item = impl Borrow<Instruction>>,
+    mut on_cycle: impl FnMut(i32, i32, &Grid<char>),
+) -> Grid<char> {
+    let mut screen = Grid::filled(SCREEN_SIZE, '.');
     let mut cycle: i32 = 0;
     let mut register: i32 = 1;
 
     for instruction in instructions {
         let instruction = instruction.borrow();
         for _ in 0..instruction.get_duration() {
-            let column = cycle % 40;
-            let sprite = register - 1..=register + 1;
-            print!("{}", if sprite.contains(&column) { '#' } else { '.' });
-            cycle += 1;
-            if cycle % 40 == 0 {
-                print!("\n");
+            let pixel = Pair {
+                x: cycle % SCREEN_SIZE.x,
+                y: cycle / SCREEN_SIZE.x,
+            };
+            if register.abs_diff(pixel.x) <= 1 {
+                screen.set(pixel, '#');
             }
+            cycle += 1;
+            on_cycle(cycle, register, &screen);
         }
         instruction.execute(&mut register);
     }
+
+    screen
+}
+
+/// Lit pixels, with the sprite showing on the row being drawn.
+fn draw_screen(screen: &Grid<char>, cycle: i32, register: i32) -> Image {
+    let row = (cycle - 1) / SCREEN_SIZE.x;
+    Grid::from_fn(SCREEN_SIZE, |Pair { x, y }| {
+        if screen[Pair { x, y }] == '#' {
+            Rgb(240, 210, 80)
+        } else if y == row && register.abs_diff(x) <= 1 {
+            Rgb(70, 70, 120)
+        } else {
+            Rgb(20, 20, 30)
+        }
+    })
+    .into()
 }
 
 #[cfg(test)]
@@ -245,6 +336,47 @@ noop";
     #[test]
     fn solution_p1() {
         let solution = solve_p1(INPUT.lines().map(Instruction::parse).map(Result::unwrap));
-        assert_eq!(solution, 13140);
+        assert_eq!(solution, Some(13140));
+    }
+
+    #[test]
+    fn overflows() {
+        let error = Day10::parse("addx 5\naddx 2147483647\n").unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        let instructions =
+            Day10::parse(&format!("addx 999999999\n{}", "noop\n".repeat(20))).unwrap();
+        assert!(Day10::part1(&instructions).is_err());
+    }
+
+    #[test]
+    fn solution_p2() {
+        let solution = solve_p2(INPUT.lines().map(Instruction::parse).map(Result::unwrap));
+        assert_eq!(
+            solution.to_string(),
+            "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######....."
+        );
+    }
+    #[test]
+    fn recording() {
+        let instructions: Vec<_> = INPUT
+            .lines()
+            .map(Instruction::parse)
+            .map(Result::unwrap)
+            .collect();
+        let mut recorder = Recorder::new(1);
+        Day10::record(&instructions, 1, &mut recorder).unwrap();
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 240);
+        assert_eq!(
+            frames[219].caption,
+            "Cycle 220: X = 18, signal strength 13140"
+        );
+        assert!(Day10::record(&instructions, 3, &mut recorder).is_err());
     }
 }
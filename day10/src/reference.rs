@@ -0,0 +1,56 @@
+//! Naive solutions, checked against the real ones on generated inputs.
+
+use common::{property::assert_matches_reference, Answer};
+
+use crate::Day10;
+
+/// Value of the register during each cycle, the first one being at index 0.
+fn register(input: &str) -> Option<Vec<i32>> {
+    let mut values = Vec::new();
+    let mut register = 1;
+    for line in input.lines() {
+        if line == "noop" {
+            values.push(register);
+        } else {
+            let value: i32 = line.strip_prefix("addx ")?.parse().ok()?;
+            values.extend([register, register]);
+            register += value;
+        }
+    }
+    Some(values)
+}
+
+fn part1(input: &str) -> Option<i32> {
+    let register = register(input)?;
+    let strength = [20, 60, 100, 140, 180, 220]
+        .into_iter()
+        .filter_map(|cycle| Some(cycle as i32 * register.get(cycle - 1)?))
+        .sum();
+    Some(strength)
+}
+
+/// Lights each of the 240 pixels whose column is within a pixel of the register.
+fn part2(input: &str) -> Option<Answer> {
+    let register = register(input)?;
+    let rows = (0..6)
+        .map(|row| {
+            (0..40)
+                .map(|column| match register.get(row * 40 + column) {
+                    Some(x) if (x - column as i32).abs() <= 1 => '#',
+                    _ => '.',
+                })
+                .collect()
+        })
+        .collect();
+    Some(Answer::Rows(rows))
+}
+
+#[test]
+fn part1_matches_reference() {
+    assert_matches_reference::<Day10, _>(1, part1);
+}
+
+#[test]
+fn part2_matches_reference() {
+    assert_matches_reference::<Day10, _>(2, part2);
+}
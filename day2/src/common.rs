@@ -1,3 +1,4 @@
+use ::common::{error::Context, scan, Error};
 use std::borrow::Borrow;
 
 pub trait Round {
@@ -19,12 +20,12 @@ impl RoundResult {
             RoundResult::Loss => 0,
         }
     }
-    pub fn parse(character: &char) -> Result<Self, &'static str> {
+    pub fn parse(character: &char) -> Result<Self, Error> {
         match character {
             'X' => Ok(RoundResult::Loss),
             'Y' => Ok(RoundResult::Draw),
             'Z' => Ok(RoundResult::Win),
-            _ => Err("Unknown character"),
+            _ => Err("Unknown character".into()),
         }
     }
 }
@@ -44,12 +45,12 @@ impl Move {
             Move::Scissors => 3,
         }
     }
-    pub fn parse(character: &char) -> Result<Self, &'static str> {
+    pub fn parse(character: &char) -> Result<Self, Error> {
         match character {
             'A' | 'X' => Ok(Move::Rock),
             'B' | 'Y' => Ok(Move::Paper),
             'C' | 'Z' => Ok(Move::Scissors),
-            _ => Err("Unknown character"),
+            _ => Err("Unknown character".into()),
         }
     }
 }
@@ -64,15 +65,13 @@ impl From<Move> for i8 {
     }
 }
 
+/// Moves wrap around, each one beating the previous one: -1 is Scissors and 3 is Rock again.
 impl From<i8> for Move {
     fn from(value: i8) -> Self {
-        match value {
+        match value.rem_euclid(3) {
             0 => Move::Rock,
             1 => Move::Paper,
-            2 => Move::Scissors,
-            _ => {
-                panic!("No Move value matching number");
-            }
+            _ => Move::Scissors,
         }
     }
 }
@@ -80,26 +79,30 @@ impl From<i8> for Move {
 #[derive(Debug, PartialEq)]
 pub struct Letters(pub char, pub char);
 
-pub fn parse_lines<I>(lines: I) -> Result<Vec<Letters>, &'static str>
+pub fn parse_lines<I>(lines: I) -> Result<Vec<Letters>, Error>
 where
     I: IntoIterator,
     I::Item: Borrow<str>,
 {
     lines
         .into_iter()
-        .map(|line| {
+        .enumerate()
+        .map(|(index, line)| {
             let line: &str = line.borrow();
-            let a = line.chars().next().ok_or("No character has been found 0")?;
-            let b = line.chars().nth(2).ok_or("No character has been found 2")?;
-            Ok(Letters(a, b))
+            parse_letters(line).at_line(index + 1, line)
         })
         .collect()
 }
 
+fn parse_letters(line: &str) -> Result<Letters, Error> {
+    let (a, b) = scan!("{} {}", line)?;
+    Ok(Letters(a, b))
+}
+
 pub fn solve<R: Round>(
-    letters: &Vec<Letters>,
-    parser: fn(letters: &Letters) -> Result<R, &'static str>,
-) -> Result<u32, &'static str> {
+    letters: &[Letters],
+    parser: fn(letters: &Letters) -> Result<R, Error>,
+) -> Result<u32, Error> {
     let rounds = letters.iter().map(parser).collect::<Result<Vec<_>, _>>()?;
 
     let sum = rounds.iter().map(Round::points).sum();
@@ -124,4 +127,11 @@ C Z";
             vec![Letters('A', 'Y'), Letters('B', 'X'), Letters('C', 'Z'),]
         )
     }
+
+    #[test]
+    fn moves_wrap_around() {
+        assert_eq!(Move::from(-1), Move::Scissors);
+        assert_eq!(Move::from(3), Move::Rock);
+        assert_eq!(Move::from(i8::from(Move::Paper)), Move::Paper);
+    }
 }
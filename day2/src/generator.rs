@@ -0,0 +1,14 @@
+use std::fmt::Write;
+
+use ::common::rng::Rng;
+
+/// Strategy guide of `size` rounds.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let opponent = rng.choose(&['A', 'B', 'C']);
+        let response = rng.choose(&['X', 'Y', 'Z']);
+        writeln!(input, "{opponent} {response}").unwrap();
+    }
+    input
+}
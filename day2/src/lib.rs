@@ -1,20 +1,37 @@
 mod common;
+mod generator;
 mod p1;
 mod p2;
+#[cfg(test)]
+mod reference;
 
-use self::common::{parse_lines, solve};
+use self::common::{parse_lines, solve, Letters};
+use ::common::{rng::Rng, Error, Solution};
 use p1::RoundPart1;
 use p2::RoundPart2;
-use ::common::lines_from_stdin;
 
-pub fn run() -> Result<(), &'static str> {
-    let letters = parse_lines(lines_from_stdin())?;
+pub struct Day2;
 
-    let solution1 = solve(&letters, RoundPart1::parse)?;
-    println!("Part 1 solution: {}", solution1);
+impl Solution for Day2 {
+    const DAY: u8 = 2;
 
-    let solution2 = solve(&letters, RoundPart2::parse)?;
-    println!("Part 2 solution: {}", solution2);
+    type Input = Vec<Letters>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_lines(input.lines())
+    }
+
+    fn part1(letters: &Self::Input) -> Result<u32, Error> {
+        solve(letters, RoundPart1::parse)
+    }
+
+    fn part2(letters: &Self::Input) -> Result<u32, Error> {
+        solve(letters, RoundPart2::parse)
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Result<String, Error> {
+        Ok(generator::generate(size, rng))
+    }
 }
@@ -1,5 +1,5 @@
-use day2::run;
+use std::process::ExitCode;
 
-fn main() -> Result<(), &'static str> {
-    run()
+fn main() -> ExitCode {
+    common::solution::main(&day2::Day2)
 }
@@ -1,4 +1,5 @@
 use super::common::{Letters, Move, Round, RoundResult};
+use ::common::Error;
 
 #[derive(Debug, PartialEq)]
 pub struct RoundPart1 {
@@ -8,18 +9,19 @@ pub struct RoundPart1 {
 
 impl RoundPart1 {
     fn result(&self) -> RoundResult {
-        let me = self.me as i8;
-        let opponent = self.opponent as i8;
-        match me - opponent {
-            0 => RoundResult::Draw,
-            1 => RoundResult::Win,
-            -1 => RoundResult::Loss,
-            2 => RoundResult::Loss,
-            -2 => RoundResult::Win,
-            _ => panic!("Undefined behavior"),
+        match (self.me, self.opponent) {
+            (Move::Rock, Move::Scissors)
+            | (Move::Paper, Move::Rock)
+            | (Move::Scissors, Move::Paper) => RoundResult::Win,
+            (Move::Rock, Move::Rock)
+            | (Move::Paper, Move::Paper)
+            | (Move::Scissors, Move::Scissors) => RoundResult::Draw,
+            (Move::Rock, Move::Paper)
+            | (Move::Paper, Move::Scissors)
+            | (Move::Scissors, Move::Rock) => RoundResult::Loss,
         }
     }
-    pub fn parse(letters: &Letters) -> Result<Self, &'static str> {
+    pub fn parse(letters: &Letters) -> Result<Self, Error> {
         Ok(Self {
             opponent: Move::parse(&letters.0)?,
             me: Move::parse(&letters.1)?,
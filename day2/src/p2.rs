@@ -1,12 +1,11 @@
 use super::common::{Letters, Move, Round, RoundResult};
+use ::common::Error;
 
 pub struct RoundPart2 {
     opponent: Move,
     result: RoundResult,
 }
 
-const MODULO: i8 = 3;
-
 impl RoundPart2 {
     fn my_move(&self) -> Move {
         let offset: i8 = match self.result {
@@ -14,14 +13,7 @@ impl RoundPart2 {
             RoundResult::Draw => 0,
             RoundResult::Loss => -1,
         };
-        let mut a: i8 = i8::from(self.opponent) + offset;
-        if a < 0 {
-            a += MODULO;
-        }
-        if a >= MODULO {
-            a -= MODULO;
-        }
-        Move::from(a)
+        Move::from(i8::from(self.opponent) + offset)
     }
 }
 
@@ -32,7 +24,7 @@ impl Round for RoundPart2 {
 }
 
 impl RoundPart2 {
-    pub fn parse(letters: &Letters) -> Result<Self, &'static str> {
+    pub fn parse(letters: &Letters) -> Result<Self, Error> {
         Ok(Self {
             opponent: Move::parse(&letters.0)?,
             result: RoundResult::parse(&letters.1)?,
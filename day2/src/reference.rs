@@ -0,0 +1,58 @@
+//! Naive solutions, checked against the real ones on generated inputs.
+
+use ::common::property::assert_matches_reference;
+
+use crate::Day2;
+
+/// Sum of the scores of each round, looked up in `table` by `"{theirs} {ours}"`.
+fn total(input: &str, table: &[(&str, u32)]) -> Option<u32> {
+    input
+        .lines()
+        .map(|line| {
+            let (_, score) = table.iter().find(|(round, _)| *round == line)?;
+            Some(*score)
+        })
+        .sum()
+}
+
+/// Our shape then the outcome, for every round.
+fn part1(input: &str) -> Option<u32> {
+    let table = [
+        ("A X", 1 + 3),
+        ("A Y", 2 + 6),
+        ("A Z", 3),
+        ("B X", 1),
+        ("B Y", 2 + 3),
+        ("B Z", 3 + 6),
+        ("C X", 1 + 6),
+        ("C Y", 2),
+        ("C Z", 3 + 3),
+    ];
+    total(input, &table)
+}
+
+/// The shape needed for the outcome, then the outcome.
+fn part2(input: &str) -> Option<u32> {
+    let table = [
+        ("A X", 3),
+        ("A Y", 1 + 3),
+        ("A Z", 2 + 6),
+        ("B X", 1),
+        ("B Y", 2 + 3),
+        ("B Z", 3 + 6),
+        ("C X", 2),
+        ("C Y", 3 + 3),
+        ("C Z", 1 + 6),
+    ];
+    total(input, &table)
+}
+
+#[test]
+fn part1_matches_reference() {
+    assert_matches_reference::<Day2, _>(1, part1);
+}
+
+#[test]
+fn part2_matches_reference() {
+    assert_matches_reference::<Day2, _>(2, part2);
+}
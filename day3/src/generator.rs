@@ -0,0 +1,37 @@
+use common::rng::Rng;
+
+/// Rucksacks by groups of three, `size` of them rounded up. Each rucksack has a single item in
+/// both compartments, and each group a single item, its badge, in all three rucksacks.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut input = String::new();
+
+    for _ in 0..size.div_ceil(3) {
+        // Items of the group are never shared between rucksacks, apart from the badge
+        rng.shuffle(&mut items);
+        let badge = items[0];
+        for pool in items[1..].chunks(17).take(3) {
+            let duplicate = if rng.chance(0.2) { badge } else { pool[0] };
+            let (left_only, right_only) = pool[1..].split_at(8);
+            let half = rng.range(2..=12) as usize;
+
+            let mut left = vec![duplicate];
+            if duplicate != badge {
+                left.push(badge);
+            }
+            while left.len() < half {
+                left.push(*rng.choose(left_only));
+            }
+            let mut right = vec![duplicate];
+            while right.len() < half {
+                right.push(*rng.choose(right_only));
+            }
+
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+            input.extend(left.into_iter().chain(right));
+            input.push('\n');
+        }
+    }
+    input
+}
@@ -1,27 +1,73 @@
-#![feature(iter_array_chunks)]
-use common::lines_from_file;
+use common::{bitset::BitSet, error::Context, iter::IterExt, rng::Rng, Error, Solution};
 use std::borrow::Borrow;
 
-pub fn run(filename: &str) -> Result<(), &'static str> {
-    let solution_p1 = solve_p1(lines_from_file(filename)?)?;
-    let solution_p2 = solve_p2(lines_from_file(filename)?)?;
+mod generator;
+#[cfg(test)]
+mod reference;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                check_rucksack(line)
+                    .at_line(index + 1, line)
+                    .map(|()| line.to_string())
+            })
+            .collect()
+    }
 
-    println!("Part 1 solution: {}", solution_p1);
-    println!("Part 2 solution: {}", solution_p2);
+    fn part1(rucksacks: &Self::Input) -> Result<u32, Error> {
+        solve_p1(rucksacks.iter().map(String::as_str))
+    }
 
-    Ok(())
+    fn part2(rucksacks: &Self::Input) -> Result<u32, Error> {
+        solve_p2(rucksacks.iter().map(String::as_str))
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Result<String, Error> {
+        Ok(generator::generate(size, rng))
+    }
 }
 
-fn build_base(definition: &&str) -> Result<u64, &'static str> {
-    let mut result: u64 = 0;
-    for char in definition.chars() {
-        let priority = priority_of_item(char)?;
-        result |= 1u64 << priority;
+/// Fails unless `line` holds letters only, as many in both compartments.
+fn check_rucksack(line: &str) -> Result<(), Error> {
+    if let Some((start, item)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        let item_text = &line[start..start + item.len_utf8()];
+        return Err(Error::at(
+            format!("Couldn't parse item {item:?}"),
+            line,
+            item_text,
+        ));
     }
-    Ok(result)
+    if !line.len().is_multiple_of(2) {
+        return Err(Error::at(
+            "Both compartments should hold as many items",
+            line,
+            line,
+        ));
+    }
+    Ok(())
+}
+
+/// Priorities of the items in a compartment.
+fn build_base(definition: &&str) -> Result<BitSet, Error> {
+    definition
+        .chars()
+        .map(|char| priority_of_item(char).map(|priority| priority as usize))
+        .collect()
 }
 
-fn priority_of_item(char: char) -> Result<u32, &'static str> {
+fn priority_of_item(char: char) -> Result<u32, Error> {
     let item = char as u32;
     if (65..=90).contains(&item) {
         return Ok(item - 38);
@@ -29,47 +75,39 @@ fn priority_of_item(char: char) -> Result<u32, &'static str> {
     if (97..=122).contains(&item) {
         return Ok(item - 96);
     }
-    Err("Couldn't parse item")
+    Err(format!("Couldn't parse item {char:?}").into())
 }
 
-fn solve_p1(lines: impl Iterator<Item = impl Borrow<str>>) -> Result<u32, &'static str> {
-    Ok(lines
+fn solve_p1(lines: impl Iterator<Item = impl Borrow<str>>) -> Result<u32, Error> {
+    lines
         .map(|line| {
             let line = line.borrow();
-            let (left, right) = line.split_at(line.len() / 2);
+            let (left, right) = line
+                .split_at_checked(line.len() / 2)
+                .ok_or_else(|| Error::at("Items should be ASCII letters", line, line))?;
             get_duplicated_item_priority([left, right])
         })
-        .collect::<Result<Vec<_>, _>>()?
-        .iter()
-        .sum())
+        .sum()
 }
 
-fn solve_p2(lines: impl Iterator<Item = impl Borrow<str>>) -> Result<u32, &'static str> {
-    Ok(lines
-        .array_chunks::<3>()
+fn solve_p2(lines: impl Iterator<Item = impl Borrow<str>>) -> Result<u32, Error> {
+    lines
+        .fixed_chunks::<3>()
         .map(|[a, b, c]| get_duplicated_item_priority([a.borrow(), b.borrow(), c.borrow()]))
-        .collect::<Result<Vec<_>, _>>()?
-        .iter()
-        .sum())
+        .sum()
 }
 
-fn get_duplicated_item_priority<const T: usize>(items: [&str; T]) -> Result<u32, &'static str> {
-    let mut iter = items.iter();
-    let first = iter.next().ok_or("Not enough items")?;
-
-    let a = iter
+fn get_duplicated_item_priority<const T: usize>(items: [&str; T]) -> Result<u32, Error> {
+    let common = items
+        .iter()
         .map(build_base)
-        .reduce(|a, b| Ok(a? & b?))
+        .reduce(|a, b| Ok(&a? & &b?))
         .ok_or("Not enough items")??;
 
-    for char in first.chars() {
-        let priority = priority_of_item(char)?;
-        if (a & 1u64 << priority) != 0 {
-            return Ok(priority);
-        }
+    match common.iter().next() {
+        Some(priority) => Ok(priority as u32),
+        None => Err("Could not find any common item".into()),
     }
-
-    Err("Could not find any common item")
 }
 
 #[cfg(test)]
@@ -86,11 +124,11 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
 
     #[test]
     fn priorities_parsing() {
-        assert!(priority_of_item('a') == Ok(1));
-        assert!(priority_of_item('b') == Ok(2));
-        assert!(priority_of_item('z') == Ok(26));
-        assert!(priority_of_item('A') == Ok(27));
-        assert!(priority_of_item('Z') == Ok(52));
+        assert!(matches!(priority_of_item('a'), Ok(1)));
+        assert!(matches!(priority_of_item('b'), Ok(2)));
+        assert!(matches!(priority_of_item('z'), Ok(26)));
+        assert!(matches!(priority_of_item('A'), Ok(27)));
+        assert!(matches!(priority_of_item('Z'), Ok(52)));
     }
 
     #[test]
@@ -104,4 +142,28 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
         let solution = solve_p2(INPUT.lines()).unwrap();
         assert!(solution == 70);
     }
+
+    #[test]
+    fn non_ascii() {
+        assert!(solve_p1(["ab秧c"].into_iter()).is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Day3::parse("abAB\nab秧c\n").unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.span(), Some(2..5));
+
+        let error = Day3::parse("abAB\nabc\n").unwrap_err();
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.span(), Some(0..3));
+    }
+
+    #[test]
+    fn generated() {
+        let input = generator::generate(30, &mut Rng::new(1));
+        assert_eq!(input.lines().count(), 30);
+        assert!(solve_p1(input.lines()).is_ok());
+        assert!(solve_p2(input.lines()).is_ok());
+    }
 }
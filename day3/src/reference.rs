@@ -0,0 +1,50 @@
+//! Naive solutions, checked against the real ones on generated inputs.
+
+use common::property::assert_matches_reference;
+
+use crate::Day3;
+
+fn priority(item: char) -> Option<u32> {
+    let position = ('a'..='z').chain('A'..='Z').position(|c| c == item)?;
+    Some(position as u32 + 1)
+}
+
+/// Compares every item of the first compartment with every item of the second one.
+fn part1(input: &str) -> Option<u32> {
+    input
+        .lines()
+        .map(|line| {
+            let (left, right) = line.split_at(line.len() / 2);
+            let shared = left
+                .chars()
+                .find(|&item| right.chars().any(|c| c == item))?;
+            priority(shared)
+        })
+        .sum()
+}
+
+fn part2(input: &str) -> Option<u32> {
+    let lines: Vec<&str> = input.lines().collect();
+    if !lines.len().is_multiple_of(3) {
+        return None;
+    }
+    lines
+        .chunks(3)
+        .map(|group| {
+            let badge = group[0]
+                .chars()
+                .find(|&item| group[1].contains(item) && group[2].contains(item))?;
+            priority(badge)
+        })
+        .sum()
+}
+
+#[test]
+fn part1_matches_reference() {
+    assert_matches_reference::<Day3, _>(1, part1);
+}
+
+#[test]
+fn part2_matches_reference() {
+    assert_matches_reference::<Day3, _>(2, part2);
+}
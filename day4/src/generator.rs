@@ -0,0 +1,17 @@
+use std::fmt::Write;
+
+use common::rng::Rng;
+
+/// `size` pairs of section assignments.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut assignment = || {
+            let start = rng.range(1..=99);
+            (start, rng.range(start..=99))
+        };
+        let ((a, b), (c, d)) = (assignment(), assignment());
+        writeln!(input, "{a}-{b},{c}-{d}").unwrap();
+    }
+    input
+}
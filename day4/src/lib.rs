@@ -1,72 +1,83 @@
-use common::lines_from_file;
-use lazy_static::lazy_static;
-use regex::Regex;
+use common::{
+    error::Context,
+    interval::{Interval, RangeSet},
+    rng::Rng,
+    scan, Error, Solution,
+};
 use std::borrow::Borrow;
 
-pub fn run(filename: &str) -> Result<(), &'static str> {
-    let solution1 = solve(lines_from_file(filename)?, p1_solver)?;
-    let solution2 = solve(lines_from_file(filename)?, p2_solver)?;
-    println!("Solution 1: {solution1}");
-    println!("Solution 2: {solution2}");
-    Ok(())
-}
+mod generator;
+#[cfg(test)]
+mod reference;
 
-struct Line {
-    left: Range,
-    right: Range,
-}
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Line>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse(input.lines())
+    }
+
+    fn part1(lines: &Self::Input) -> Result<usize, Error> {
+        Ok(solve(lines, p1_solver))
+    }
+
+    fn part2(lines: &Self::Input) -> Result<usize, Error> {
+        Ok(solve(lines, p2_solver))
+    }
 
-struct Range {
-    start: i16,
-    end: i16,
+    fn generate(size: usize, rng: &mut Rng) -> Result<String, Error> {
+        Ok(generator::generate(size, rng))
+    }
 }
 
-lazy_static! {
-    static ref RE: Regex = Regex::new(r"^(\d+)-(\d+),(\d+)-(\d+)$").unwrap();
+pub struct Line {
+    left: Interval<i32>,
+    right: Interval<i32>,
 }
 
 impl Line {
-    fn parse(line: &str) -> Result<Self, &'static str> {
-        let a = RE.captures(line).ok_or("Line doesn't match pattern")?;
+    fn parse(line: &str) -> Result<Self, Error> {
+        // Sections are read narrower than they are stored, so that lengths can't overflow
+        let (left_start, left_end, right_start, right_end) =
+            scan!("{}-{},{}-{}", line => u16, u16, u16, u16)?;
 
         Ok(Line {
-            left: Range {
-                start: expect_number(&a[1])?,
-                end: expect_number(&a[2])?,
-            },
-            right: Range {
-                start: expect_number(&a[3])?,
-                end: expect_number(&a[4])?,
-            },
+            left: Interval::inclusive(left_start.into(), left_end.into()),
+            right: Interval::inclusive(right_start.into(), right_end.into()),
         })
     }
 }
 
-fn expect_number(captured: &str) -> Result<i16, &'static str> {
-    captured.parse().or(Err("Can't parse item."))
-}
-
 fn p1_solver(Line { left, right }: &Line) -> bool {
-    let left_contains_right = left.start <= right.start && left.end >= right.end;
-    let right_contains_left = left.start >= right.start && left.end <= right.end;
-    left_contains_right || right_contains_left
+    // one contains the other if together they cover no more than the largest one
+    let covered = RangeSet::from_iter([*left, *right]).len();
+    covered == left.len().max(right.len())
 }
 
 fn p2_solver(Line { left, right }: &Line) -> bool {
-    // it does not collide if right end is before left start, or right start is after left end
-    // !(right.end < left.start || right.start > left.end)
-    right.end >= left.start && right.start <= left.end
+    // they collide if together they cover less than each of them does
+    let covered = RangeSet::from_iter([*left, *right]).len();
+    covered < left.len() + right.len()
+}
+
+fn parse(lines: impl Iterator<Item = impl Borrow<str>>) -> Result<Vec<Line>, Error> {
+    lines
+        .enumerate()
+        .map(|(index, line)| {
+            let line = line.borrow();
+            Line::parse(line).at_line(index + 1, line)
+        })
+        .collect()
 }
 
-fn solve(
-    mut lines: impl Iterator<Item = impl Borrow<str>>,
-    solver: fn(&Line) -> bool,
-) -> Result<usize, &'static str> {
-    lines.try_fold(0, |acc, line| {
-        let parsed = Line::parse(line.borrow())?;
-        let is_true = solver(&parsed);
-        Ok(if is_true { acc + 1 } else { acc })
-    })
+fn solve(lines: &[Line], solver: fn(&Line) -> bool) -> usize {
+    lines.iter().filter(|line| solver(line)).count()
 }
 
 #[cfg(test)]
@@ -83,7 +94,7 @@ mod tests {
 
     #[test]
     fn solution_p1() {
-        let solution = solve(INPUT.lines(), p1_solver).unwrap();
+        let solution = solve(&parse(INPUT.lines()).unwrap(), p1_solver);
         assert!(solution == 2);
     }
 
@@ -96,7 +107,14 @@ mod tests {
 
     #[test]
     fn solution_p2() {
-        let solution = solve(INPUT.lines(), p2_solver).unwrap();
+        let solution = solve(&parse(INPUT.lines()).unwrap(), p2_solver);
         assert!(solution == 4);
     }
+
+    #[test]
+    fn extreme_sections() {
+        let line = Line::parse("0-65535,65535-65535").unwrap();
+        assert!(p1_solver(&line) && p2_solver(&line));
+        assert!(Line::parse("-1-3,2-4").is_err());
+    }
 }
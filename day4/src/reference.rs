@@ -0,0 +1,51 @@
+//! Naive solutions, checked against the real ones on generated inputs.
+
+use std::ops::RangeInclusive;
+
+use common::property::assert_matches_reference;
+
+use crate::Day4;
+
+/// Counts the pairs for which `matches` holds, comparing the sections one by one.
+fn count(
+    input: &str,
+    matches: impl Fn(RangeInclusive<u32>, RangeInclusive<u32>) -> bool,
+) -> Option<usize> {
+    let mut count = 0;
+    for line in input.lines() {
+        let numbers: Vec<u32> = line
+            .split(['-', ','])
+            .map(|number| number.parse().ok())
+            .collect::<Option<_>>()?;
+        let [a, b, c, d] = numbers[..] else {
+            return None;
+        };
+        if matches(a..=b, c..=d) {
+            count += 1;
+        }
+    }
+    Some(count)
+}
+
+fn part1(input: &str) -> Option<usize> {
+    count(input, |left, right| {
+        left.clone().all(|section| right.contains(&section))
+            || right.clone().all(|section| left.contains(&section))
+    })
+}
+
+fn part2(input: &str) -> Option<usize> {
+    count(input, |left, right| {
+        left.clone().any(|section| right.contains(&section))
+    })
+}
+
+#[test]
+fn part1_matches_reference() {
+    assert_matches_reference::<Day4, _>(1, part1);
+}
+
+#[test]
+fn part2_matches_reference() {
+    assert_matches_reference::<Day4, _>(2, part2);
+}
@@ -0,0 +1,45 @@
+use std::fmt::Write;
+
+use common::rng::Rng;
+
+/// Drawing of up to 9 stacks, followed by `size` moves (at least one) that never take every crate of a stack,
+/// so that each one has a crate on top in the end.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let count = rng.range(2..=9) as usize;
+    let mut heights: Vec<usize> = (0..count).map(|_| rng.range(1..=8) as usize).collect();
+    // At least one crate can move
+    heights[0] = heights[0].max(2);
+
+    let mut input = String::new();
+    for level in (0..*heights.iter().max().unwrap()).rev() {
+        let row: Vec<String> = heights
+            .iter()
+            .map(|&height| {
+                if level < height {
+                    format!("[{}]", (b'A' + rng.below(26) as u8) as char)
+                } else {
+                    "   ".to_string()
+                }
+            })
+            .collect();
+        writeln!(input, "{}", row.join(" ").trim_end()).unwrap();
+    }
+    let numbers: Vec<String> = (1..=count).map(|number| format!(" {number} ")).collect();
+    writeln!(input, "{}\n", numbers.join(" ")).unwrap();
+
+    for _ in 0..size.max(1) {
+        let movable: Vec<usize> = (0..count).filter(|&stack| heights[stack] > 1).collect();
+        let from = *rng.choose(&movable);
+        let to = loop {
+            let to = rng.index(count);
+            if to != from {
+                break to;
+            }
+        };
+        let crates = rng.range(1..=heights[from] as i64 - 1) as usize;
+        heights[from] -= crates;
+        heights[to] += crates;
+        writeln!(input, "move {crates} from {} to {}", from + 1, to + 1).unwrap();
+    }
+    input
+}
@@ -1,56 +1,108 @@
-use common::lines_from_file;
-use lazy_static::lazy_static;
-use regex::Regex;
-use std::borrow::Borrow;
+use common::{
+    error::Context,
+    frames::{Frame, Recorder},
+    pair::Pair,
+    render::{gradient, Image, Rgb},
+    rng::Rng,
+    scan,
+    sections::{Section, Sections},
+    trace, Error, Grid, Solution,
+};
+use std::fmt::{self, Display};
 
-type Error = &'static str;
+mod generator;
+#[cfg(test)]
+mod reference;
 
-pub fn run(filename: &str) -> Result<(), Error> {
-    let lines = lines_from_file(filename)?;
-    let (stacks, movements) = parse_input(lines)?;
+pub struct Day5;
 
-    let result1 = solve(stacks.clone(), &movements, apply_movement_p1)?;
-    let result2 = solve(stacks, &movements, apply_movement_p2)?;
+impl Solution for Day5 {
+    const DAY: u8 = 5;
 
-    println!("Result P1: {}", &result1);
-    println!("Result P2: {}", &result2);
-    Ok(())
+    type Input = (Stacks, Vec<Movement>);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_input(input)
+    }
+
+    fn part1((stacks, movements): &Self::Input) -> Result<String, Error> {
+        solve(stacks.clone(), movements, apply_movement_p1)
+    }
+
+    fn part2((stacks, movements): &Self::Input) -> Result<String, Error> {
+        solve(stacks.clone(), movements, apply_movement_p2)
+    }
+
+    fn record(
+        (stacks, movements): &Self::Input,
+        part: u8,
+        recorder: &mut Recorder,
+    ) -> Result<(), Error> {
+        let apply_movement = match part {
+            1 => apply_movement_p1,
+            2 => apply_movement_p2,
+            _ => return Err(format!("Day {} has no part {part}", Self::DAY).into()),
+        };
+        let mut stacks = stacks.clone();
+        let height = stacks.iter().map(Vec::len).sum();
+
+        recorder.push(|| Frame::new(draw_stacks(&stacks, height), "Start"));
+        for movement in movements {
+            apply_movement(&mut stacks, movement).context(format!("Couldn't {movement}"))?;
+            recorder.push(|| Frame::new(draw_stacks(&stacks, height), movement.to_string()));
+        }
+        Ok(())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Result<String, Error> {
+        Ok(generator::generate(size, rng))
+    }
 }
 
 type Stacks = Vec<Stack>;
 type Stack = Vec<char>;
 
-fn parse_input(
-    mut lines_iter: impl Iterator<Item = impl Borrow<str>>,
-) -> Result<(Stacks, Vec<Movement>), Error> {
-    let stacks = parse_stacks(&mut lines_iter)?;
-    let movements = parse_movements(&mut lines_iter)?;
+fn parse_input(input: &str) -> Result<(Stacks, Vec<Movement>), Error> {
+    let mut sections = Sections::new(input);
+    let stacks = parse_stacks(&sections.expect("stacks drawing")?)?;
+    let movements = sections
+        .expect("movements")?
+        .parse_lines(|line| Movement::parse(line, stacks.len()))?;
+    sections.end()?;
     Ok((stacks, movements))
 }
 
-fn parse_stacks(lines_iter: &mut impl Iterator<Item = impl Borrow<str>>) -> Result<Stacks, Error> {
-    let stacks_lines: Vec<_> = lines_iter
-        .by_ref()
-        .take_while(|a| !a.borrow().is_empty())
-        .collect();
-
-    let mut from_bottom = stacks_lines.iter().rev();
+fn parse_stacks(drawing: &Section) -> Result<Stacks, Error> {
+    let mut from_bottom = drawing.numbered_lines().rev();
 
-    let stack_count = {
-        let numbers_line = from_bottom.next().ok_or("Missing line with numbers.")?;
-        let last_number = numbers_line.borrow().split_whitespace().last();
-        last_number
-            .and_then(|ln| ln.parse::<usize>().ok())
-            .ok_or("Couldn't parse last number")
-    }?;
+    let (number, numbers_line) = from_bottom.next().ok_or("Missing line with numbers.")?;
+    let last_number = numbers_line.split_whitespace().last().unwrap_or_default();
+    let stack_count = last_number.parse::<usize>().map_err(|e| {
+        Error::at("Couldn't parse last number", numbers_line, last_number)
+            .at_line(number, numbers_line)
+            .with_source(e)
+    })?;
+    if stack_count != numbers_line.split_whitespace().count() {
+        let error = Error::at(
+            "Stacks should be numbered from 1",
+            numbers_line,
+            last_number,
+        );
+        return Err(error.at_line(number, numbers_line));
+    }
 
     let mut stacks: Stacks = vec![vec![]; stack_count];
 
-    for line in from_bottom {
-        let chars = line.borrow().chars().skip(1).step_by(4).enumerate();
+    for (number, line) in from_bottom {
+        let chars = line.chars().skip(1).step_by(4).enumerate();
         for (index, letter) in chars {
             if letter.is_alphanumeric() {
-                stacks[index].push(letter)
+                stacks
+                    .get_mut(index)
+                    .ok_or_else(|| Error::new("Crate beyond the last stack").at_line(number, line))?
+                    .push(letter)
             }
         }
     }
@@ -58,21 +110,14 @@ fn parse_stacks(lines_iter: &mut impl Iterator<Item = impl Borrow<str>>) -> Resu
     Ok(stacks)
 }
 
-fn parse_movements(
-    lines_iter: &mut impl Iterator<Item = impl Borrow<str>>,
-) -> Result<Vec<Movement>, Error> {
-    lines_iter
-        .map(|line| Movement::parse(line.borrow()))
-        .collect()
-}
-
 fn solve(
     mut stacks: Stacks,
-    movements: &Vec<Movement>,
+    movements: &[Movement],
     apply_movement: fn(&mut Stacks, &Movement) -> Result<(), Error>,
 ) -> Result<String, Error> {
     for movement in movements {
-        apply_movement(&mut stacks, movement)?;
+        apply_movement(&mut stacks, movement).context(format!("Couldn't {movement}"))?;
+        trace!("{movement}: {}", describe_stacks(&stacks));
     }
     get_top_crates(&stacks)
 }
@@ -81,6 +126,10 @@ fn apply_movement_p1(
     stacks: &mut Stacks,
     Movement { from, to, crates }: &Movement,
 ) -> Result<(), Error> {
+    // Checked first, as moving a stack onto itself never runs out of crates
+    if *crates > stacks[from - 1].len() {
+        return Err("Not enough crates on this stack".into());
+    }
     for _ in 0..*crates {
         let moved_crate = stacks[from - 1].pop().ok_or("Cannot take crate")?;
         stacks[to - 1].push(moved_crate);
@@ -101,41 +150,71 @@ fn apply_movement_p2(
     Ok(())
 }
 
-lazy_static! {
-    static ref MOVEMENT_PATTERN: Regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+/// Crates of each stack from the bottom, such as `[ZN] [MCD] [P]`.
+fn describe_stacks(stacks: &Stacks) -> String {
+    let stacks: Vec<String> = stacks
+        .iter()
+        .map(|stack| format!("[{}]", stack.iter().collect::<String>()))
+        .collect();
+    stacks.join(" ")
+}
+
+/// Crates as pixels coloured by letter, from the bottom of a picture `height` crates high, with
+/// a column of space between stacks.
+fn draw_stacks(stacks: &Stacks, height: usize) -> Image {
+    let color = gradient(Rgb(230, 90, 40), Rgb(60, 130, 230), 0, 25);
+    let size = Pair {
+        x: (2 * stacks.len()).saturating_sub(1) as i32,
+        y: height as i32,
+    };
+    Grid::from_fn(size, |Pair { x, y }| {
+        let depth = height - 1 - y as usize;
+        match stacks[x as usize / 2].get(depth) {
+            Some(&letter) if x % 2 == 0 => color(&(letter as u8).saturating_sub(b'A')),
+            _ => Rgb::BLACK,
+        }
+    })
+    .into()
 }
 
 #[derive(PartialEq, Debug)]
-struct Movement {
+pub struct Movement {
     crates: usize,
     from: usize,
     to: usize,
 }
 impl Movement {
-    fn parse(line: &str) -> Result<Self, Error> {
-        let captures = MOVEMENT_PATTERN
-            .captures(line)
-            .ok_or("Couldn't parse movement.")?;
+    /// Fails when the movement involves a stack missing from the `count` ones drawn, the error
+    /// pointing at its number.
+    fn parse(line: &str, count: usize) -> Result<Self, Error> {
+        let captures = scan::captures("move {} from {} to {}", line, 3)?;
+        let stack = |capture| {
+            let stack = scan::parse(line, capture)?;
+            if (1..=count).contains(&stack) {
+                Ok(stack)
+            } else {
+                Err(Error::at(format!("No stack {stack}"), line, capture))
+            }
+        };
         Ok(Movement {
-            crates: expect_number(captures.get(1))?,
-            from: expect_number(captures.get(2))?,
-            to: expect_number(captures.get(3))?,
+            crates: scan::parse(line, captures[0])?,
+            from: stack(captures[1])?,
+            to: stack(captures[2])?,
         })
     }
 }
 
-fn expect_number(captured: Option<regex::Match>) -> Result<usize, Error> {
-    captured
-        .ok_or("Match not found")?
-        .as_str()
-        .parse()
-        .or(Err("Couldn't parse number"))
+impl Display for Movement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Movement { crates, from, to } = self;
+        write!(f, "move {crates} from {from} to {to}")
+    }
 }
 
 fn get_top_crates(stacks: &Stacks) -> Result<String, Error> {
     stacks
         .iter()
-        .map(|s| s.last().ok_or("Can't get top crate: stack is empty"))
+        .map(|s| s.last().ok_or("Can't get top crate: stack is empty".into()))
         .collect()
 }
 
@@ -156,7 +235,7 @@ move 1 from 1 to 2
 
     #[test]
     fn stacks_parsing() {
-        let stacks = parse_stacks(&mut INPUT.lines()).unwrap();
+        let stacks = parse_stacks(&Sections::new(INPUT).next().unwrap()).unwrap();
         let expected = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
         assert_eq!(stacks, expected);
     }
@@ -164,7 +243,7 @@ move 1 from 1 to 2
     #[test]
     fn movement_parsing() {
         assert_eq!(
-            Movement::parse("move 2 from 3 to 1").unwrap(),
+            Movement::parse("move 2 from 3 to 1", 3).unwrap(),
             Movement {
                 crates: 2,
                 from: 3,
@@ -173,17 +252,63 @@ move 1 from 1 to 2
         );
     }
 
+    #[test]
+    fn movement_error_location() {
+        let input = INPUT.replace("move 3 from 1 to 3", "move 3 from x to 3");
+        let error = parse_input(&input).unwrap_err();
+        assert_eq!(error.line(), Some(7));
+        assert_eq!(error.snippet(), Some("move 3 from x to 3"));
+        assert_eq!(error.span(), Some(12..13));
+    }
+
+    #[test]
+    fn missing_stack() {
+        let input = INPUT.replace("move 1 from 2 to 1", "move 1 from 0 to 1");
+        let error = parse_input(&input).unwrap_err();
+        assert_eq!(error.line(), Some(6));
+        assert_eq!(error.span(), Some(12..13));
+
+        let input = INPUT.replace("move 1 from 1 to 2", "move 1 from 1 to 4");
+        assert_eq!(parse_input(&input).unwrap_err().span(), Some(17..18));
+        assert!(parse_input(&INPUT.replace("[P]", "[P] [Q]")).is_err());
+        assert!(parse_input(&INPUT.replace(" 3", " 99999999999")).is_err());
+    }
+
     #[test]
     fn solve_p1() {
-        let (stacks, movements) = parse_input(INPUT.lines()).unwrap();
+        let (stacks, movements) = parse_input(INPUT).unwrap();
         let result = solve(stacks, &movements, apply_movement_p1).unwrap();
         assert_eq!(result, "CMZ");
     }
 
+    #[test]
+    fn huge_self_move() {
+        let (stacks, movements) =
+            parse_input("[A]\n 1 \n\nmove 18446744073709551615 from 1 to 1\n").unwrap();
+        let error = solve(stacks.clone(), &movements, apply_movement_p1).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Couldn't move 18446744073709551615 from 1 to 1"
+        );
+        assert!(solve(stacks, &movements, apply_movement_p2).is_err());
+    }
+
     #[test]
     fn solve_p2() {
-        let (stacks, movements) = parse_input(INPUT.lines()).unwrap();
+        let (stacks, movements) = parse_input(INPUT).unwrap();
         let result = solve(stacks, &movements, apply_movement_p2).unwrap();
         assert_eq!(result, "MCD");
     }
+
+    #[test]
+    fn recording() {
+        let input = parse_input(INPUT).unwrap();
+        let mut recorder = Recorder::new(1);
+        Day5::record(&input, 2, &mut recorder).unwrap();
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 5);
+        assert_eq!(frames[4].caption, "move 1 from 1 to 2");
+        assert_eq!(frames[4].image.size(), Pair { x: 5, y: 6 });
+        assert!(Day5::record(&input, 3, &mut recorder).is_err());
+    }
 }
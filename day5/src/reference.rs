@@ -0,0 +1,78 @@
+//! Naive solutions, checked against the real ones on generated inputs.
+
+use common::property::assert_matches_reference;
+
+use crate::Day5;
+
+/// Crate count, then indexes of the stacks it moves from and to.
+type Move = [usize; 3];
+
+/// Stacks from bottom to top, reading the letter of every crate column by column, then the
+/// moves.
+fn parse(input: &str) -> Option<(Vec<Vec<char>>, Vec<Move>)> {
+    let (drawing, moves) = input.split_once("\n\n")?;
+    let mut rows: Vec<Vec<char>> = drawing.lines().map(|line| line.chars().collect()).collect();
+    let numbers = rows.pop()?;
+    let count = numbers.iter().filter(|c| c.is_ascii_digit()).count();
+
+    let mut stacks = vec![Vec::new(); count];
+    for row in rows.iter().rev() {
+        for (index, stack) in stacks.iter_mut().enumerate() {
+            match row.get(1 + 4 * index) {
+                Some(&letter) if letter.is_ascii_alphabetic() => stack.push(letter),
+                _ => {}
+            }
+        }
+    }
+
+    let moves = moves
+        .lines()
+        .map(|line| {
+            let words: Vec<&str> = line.split(' ').collect();
+            let [_, crates, _, from, _, to] = words[..] else {
+                return None;
+            };
+            let number = |word: &str| word.parse::<usize>().ok();
+            Some([
+                number(crates)?,
+                number(from)?.checked_sub(1)?,
+                number(to)?.checked_sub(1)?,
+            ])
+        })
+        .collect::<Option<_>>()?;
+    Some((stacks, moves))
+}
+
+/// Moves the crates, `move_stack` taking them all at once, then reads the tops.
+fn solve(input: &str, move_stack: bool) -> Option<String> {
+    let (mut stacks, moves) = parse(input)?;
+    for [crates, from, to] in moves {
+        let mut moved = Vec::new();
+        for _ in 0..crates {
+            moved.push(stacks.get_mut(from)?.pop()?);
+        }
+        if move_stack {
+            moved.reverse();
+        }
+        stacks.get_mut(to)?.extend(moved);
+    }
+    stacks.iter().map(|stack| stack.last().copied()).collect()
+}
+
+fn part1(input: &str) -> Option<String> {
+    solve(input, false)
+}
+
+fn part2(input: &str) -> Option<String> {
+    solve(input, true)
+}
+
+#[test]
+fn part1_matches_reference() {
+    assert_matches_reference::<Day5, _>(1, part1);
+}
+
+#[test]
+fn part2_matches_reference() {
+    assert_matches_reference::<Day5, _>(2, part2);
+}
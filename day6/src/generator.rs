@@ -0,0 +1,19 @@
+use common::rng::Rng;
+
+/// Datastream of `size` letters, at least 14, with markers of both lengths in it.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(14);
+    // Too few letters for a marker to show up by chance before the one placed below
+    let mut stream: Vec<char> = (0..size)
+        .map(|_| (b'a' + rng.below(3) as u8) as char)
+        .collect();
+
+    let mut marker: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut marker);
+    let start = rng.index(size - 13);
+    stream[start..start + 14].copy_from_slice(&marker[..14]);
+
+    let mut input: String = stream.into_iter().collect();
+    input.push('\n');
+    input
+}
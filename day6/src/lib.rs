@@ -1,12 +1,33 @@
-use std::fs::read_to_string;
+use common::{bitset::FixedBitSet, rng::Rng, Error, Solution};
 
-type Error = &'static str;
+mod generator;
+#[cfg(test)]
+mod reference;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-pub fn run(filename: &str) -> Result<(), Error> {
-    let input = read_to_string(filename).or(Err("Couldn't open file"))?;
-    println!("Solution P1: {}", solve_p1(&input)?);
-    println!("Solution P2: {}", solve_p2(&input)?);
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.trim_end().to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, Error> {
+        solve_p1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, Error> {
+        solve_p2(input)
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Result<String, Error> {
+        Ok(generator::generate(size, rng))
+    }
 }
 
 fn solve_p1(input: &str) -> Result<usize, Error> {
@@ -17,21 +38,22 @@ fn solve_p2(input: &str) -> Result<usize, Error> {
     solve::<14>(input)
 }
 
+/// End of the first run of `LENGTH` different bytes. Each window is checked from its end, so
+/// that a duplicate skips every window still containing both of its bytes.
 fn solve<const LENGTH: usize>(input: &str) -> Result<usize, Error> {
     let bytes = input.as_bytes();
     let mut idx = 0;
-    'str_iter: while idx < input.len() - LENGTH {
-        for i in 0..LENGTH {
-            for j in i + 1..LENGTH {
-                if bytes[idx + i] == bytes[idx + j] {
-                    idx += i + 1;
-                    continue 'str_iter;
-                }
-            }
+    while idx + LENGTH <= bytes.len() {
+        let mut seen = FixedBitSet::<4>::new();
+        match (0..LENGTH)
+            .rev()
+            .find(|&i| !seen.insert(bytes[idx + i] as usize))
+        {
+            Some(i) => idx += i + 1,
+            None => return Ok(idx + LENGTH),
         }
-        return Ok(idx + LENGTH);
     }
-    Err("No solution has been found")
+    Err("No solution has been found".into())
 }
 
 #[cfg(test)]
@@ -57,4 +79,10 @@ mod tests {
         let result = solve_p2(input).unwrap();
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn short_input() {
+        assert!(solve_p1("ab").is_err());
+        assert!(solve_p2("").is_err());
+    }
 }
@@ -1,8 +1,5 @@
-use std::env;
+use std::process::ExitCode;
 
-use day6::run;
-
-fn main() -> Result<(), &'static str> {
-    let filename = env::args().nth(1).ok_or("No filename provided.")?;
-    run(&filename)
+fn main() -> ExitCode {
+    common::solution::main(&day6::Day6)
 }
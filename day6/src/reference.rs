@@ -0,0 +1,32 @@
+//! Naive solutions, checked against the real ones on generated inputs.
+
+use common::property::assert_matches_reference;
+
+use crate::Day6;
+
+/// End of the first `length` characters all differing from each other, comparing every pair.
+fn marker(input: &str, length: usize) -> Option<usize> {
+    let chars: Vec<char> = input.trim_end().chars().collect();
+    (length..=chars.len()).find(|&end| {
+        let window = &chars[end - length..end];
+        (0..length).all(|i| (i + 1..length).all(|j| window[i] != window[j]))
+    })
+}
+
+fn part1(input: &str) -> Option<usize> {
+    marker(input, 4)
+}
+
+fn part2(input: &str) -> Option<usize> {
+    marker(input, 14)
+}
+
+#[test]
+fn part1_matches_reference() {
+    assert_matches_reference::<Day6, _>(1, part1);
+}
+
+#[test]
+fn part2_matches_reference() {
+    assert_matches_reference::<Day6, _>(2, part2);
+}
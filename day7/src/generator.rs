@@ -0,0 +1,96 @@
+use std::fmt::Write;
+
+use common::rng::Rng;
+
+#[derive(Default)]
+struct Folder {
+    name: String,
+    folders: Vec<usize>,
+    files: Vec<(String, usize)>,
+}
+
+/// Transcript exploring a tree of `size` files and directories, using between 41M and 70M of
+/// the disk so that both parts have an answer.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut folders = vec![Folder::default()];
+    for _ in 0..size {
+        let parent = rng.index(folders.len());
+        let name = loop {
+            let name = name(rng);
+            let parent = &folders[parent];
+            let taken = parent.files.iter().any(|(file, _)| *file == name)
+                || parent.folders.iter().any(|&f| folders[f].name == name);
+            if !taken {
+                break name;
+            }
+        };
+        if rng.chance(0.3) {
+            folders.push(Folder {
+                name,
+                ..Folder::default()
+            });
+            let child = folders.len() - 1;
+            folders[parent].folders.push(child);
+        } else {
+            let file_size = rng.range(1..=200_000) as usize;
+            folders[parent].files.push((name, file_size));
+        }
+    }
+
+    let used: usize = folders
+        .iter()
+        .flat_map(|folder| &folder.files)
+        .map(|(_, size)| size)
+        .sum();
+    let target = rng.range(41_000_000..=69_000_000) as usize;
+    if used < target {
+        folders[0]
+            .files
+            .push(("filler.dat".to_string(), target - used));
+    }
+
+    let mut input = "$ cd /\n".to_string();
+    explore(&folders, 0, rng, &mut input);
+    input
+}
+
+fn explore(folders: &[Folder], index: usize, rng: &mut Rng, input: &mut String) {
+    let folder = &folders[index];
+    let mut listing: Vec<String> = folder
+        .folders
+        .iter()
+        .map(|&child| format!("dir {}", folders[child].name))
+        .chain(
+            folder
+                .files
+                .iter()
+                .map(|(name, size)| format!("{size} {name}")),
+        )
+        .collect();
+    rng.shuffle(&mut listing);
+
+    input.push_str("$ ls\n");
+    for entry in listing {
+        writeln!(input, "{entry}").unwrap();
+    }
+    for &child in &folder.folders {
+        writeln!(input, "$ cd {}", folders[child].name).unwrap();
+        explore(folders, child, rng, input);
+        input.push_str("$ cd ..\n");
+    }
+}
+
+/// Lowercase name, with an extension half of the time.
+fn name(rng: &mut Rng) -> String {
+    let len = rng.range(3..=8);
+    let mut name: String = (0..len).map(|_| letter(rng)).collect();
+    if rng.chance(0.5) {
+        name.push('.');
+        name.extend((0..3).map(|_| letter(rng)));
+    }
+    name
+}
+
+fn letter(rng: &mut Rng) -> char {
+    (b'a' + rng.below(26) as u8) as char
+}
@@ -1,4 +1,10 @@
-use common::lines_from_file;
+use common::{
+    debug,
+    error::Context,
+    log::{self, Level},
+    rng::Rng,
+    scan, Error, Solution,
+};
 use std::{
     borrow::Borrow,
     cell::RefCell,
@@ -6,22 +12,43 @@ use std::{
     rc::{Rc, Weak},
 };
 
-type Error = &'static str;
-
-pub fn run(filename: &str) -> Result<(), Error> {
-    let root = parse(lines_from_file(filename)?)?;
-    print_tree(&root, 0);
-    let (total, solution_p1) = solve_p1(&root);
-    println!("Solution P1: {} (total: {total})", solution_p1);
-    let (_, solution_p2) = solve_p2(
-        &root,
-        get_missing_space(&root).ok_or("Space is not missing")?,
-    );
-    println!(
-        "Solution P2: {}",
-        solution_p2.ok_or("No dir could be removed")?
-    );
-    Ok(())
+mod generator;
+#[cfg(test)]
+mod reference;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Directory;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse(input.lines())
+    }
+
+    fn part1(root: &Self::Input) -> Result<usize, Error> {
+        let (_, solution_p1) = solve_p1(root);
+        Ok(solution_p1)
+    }
+
+    fn part2(root: &Self::Input) -> Result<usize, Error> {
+        let (_, solution_p2) =
+            solve_p2(root, get_missing_space(root).ok_or("Space is not missing")?);
+        solution_p2.ok_or_else(|| "No dir could be removed".into())
+    }
+
+    fn describe(root: &Self::Input) {
+        if log::enabled(Level::Debug) {
+            print_tree(root, 0);
+        }
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> Result<String, Error> {
+        Ok(generator::generate(size, rng))
+    }
 }
 
 const STORAGE: usize = 70000000;
@@ -67,10 +94,10 @@ fn solve_p2(dir: &Directory, removal_target: usize) -> (usize, Option<usize>) {
             Node::Directory(dir) => solve_p2(dir, removal_target),
             Node::File(file) => (file.size, None),
         })
-        .reduce(|a, b| (a.0 + b.0, [a.1, b.1].iter().filter_map(|i| i.clone()).min()))
+        .reduce(|a, b| (a.0 + b.0, [a.1, b.1].iter().filter_map(|i| *i).min()))
         .unwrap_or((0, None));
 
-    if result == None && size >= removal_target {
+    if result.is_none() && size >= removal_target {
         result = Some(size)
     }
 
@@ -82,63 +109,68 @@ fn parse(input: impl Iterator<Item = impl Borrow<str>>) -> Result<Directory, Err
     let mut current: Rc<Directory> = root.clone();
     let mut ls_output = false;
 
-    for line in input {
-        let mut line = line.borrow().split_whitespace();
+    for (index, line) in input.enumerate() {
+        let line = line.borrow();
+        parse_line(line, &root, &mut current, &mut ls_output).at_line(index + 1, line)?;
+    }
 
-        let first = line.next().ok_or("Empty line.")?;
-        if first == "$" {
-            ls_output = false;
-        }
+    // Transcripts climbing back to the root would otherwise leave it shared
+    drop(current);
+    Rc::try_unwrap(root).map_err(|_| "The root directory is still shared".into())
+}
 
-        if ls_output {
-            match first {
-                "dir" => {
-                    let name = line
-                        .next()
-                        .ok_or("Incorrect ls output: expecting directory's name.")?
-                        .to_string();
-                    let dir = Node::Directory(Rc::new(Directory::new(Some(&current))));
-                    current.children.borrow_mut().insert(name, dir);
+fn parse_line(
+    text: &str,
+    root: &Rc<Directory>,
+    current: &mut Rc<Directory>,
+    ls_output: &mut bool,
+) -> Result<(), Error> {
+    if text.starts_with('$') {
+        *ls_output = text == "$ ls";
+        if !*ls_output {
+            let (dir,): (String,) = scan!("$ cd {}", text)?;
+            match dir.as_str() {
+                ".." => {
+                    *current = current
+                        .parent
+                        .upgrade()
+                        .ok_or_else(|| Error::at("No parent !", text, &text[5..]))?;
                 }
-                size => {
-                    let size = size
-                        .parse::<usize>()
-                        .or(Err("Incorrect ls output: unknown type of node."))?;
-                    let name = line
-                        .next()
-                        .ok_or("Incorrect ls output: expecting file's name.")?
-                        .to_string();
-                    let file = Node::File(File { size });
-                    current.children.borrow_mut().insert(name, file);
+                "/" => {
+                    *current = root.clone();
                 }
-            }
-        } else {
-            match line.next().ok_or("No command...")? {
-                "cd" => match line.next().ok_or("cd command expects a name")? {
-                    ".." => {
-                        current = current.parent.upgrade().ok_or("No parent !")?.clone();
-                    }
-                    "/" => {
-                        current = root.clone();
-                    }
-                    dir => match current.clone().children.borrow().get(dir) {
-                        Some(Node::Directory(dir)) => {
-                            current = dir.clone();
+                dir => {
+                    let child = match current.children.borrow().get(dir) {
+                        Some(Node::Directory(dir)) => dir.clone(),
+                        _ => {
+                            return Err(Error::at(
+                                "cd operand is not a directory.",
+                                text,
+                                &text[5..],
+                            ))
                         }
-                        _ => return Err("cd operand is not a directory."),
-                    },
-                },
-                "ls" => {
-                    ls_output = true;
-                }
-                _ => {
-                    return Err("Unknown command.");
+                    };
+                    *current = child;
                 }
             }
         }
+    } else if !*ls_output {
+        return Err(Error::at("Expected a command.", text, text));
+    } else if text.starts_with("dir ") {
+        let (name,) = scan!("dir {}", text => String)?;
+        let dir = Node::Directory(Rc::new(Directory::new(Some(current))));
+        current.children.borrow_mut().insert(name, dir);
+    } else {
+        let (size, name) = scan!("{} {}", text => usize, String)?;
+        if size > STORAGE {
+            let digits = text.split(' ').next().unwrap_or(text);
+            return Err(Error::at("File larger than the whole disk", text, digits));
+        }
+        let file = Node::File(File { size });
+        current.children.borrow_mut().insert(name, file);
     }
 
-    Ok(Rc::try_unwrap(root).unwrap())
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -162,7 +194,7 @@ struct File {
 }
 
 #[derive(Debug)]
-struct Directory {
+pub struct Directory {
     children: RefCell<HashMap<String, Node>>,
     parent: Weak<Directory>,
 }
@@ -183,10 +215,10 @@ fn print_tree(dir: &Directory, depth: usize) {
     for (name, node) in dir.children.borrow().iter() {
         match node {
             Node::File(file) => {
-                println!("{:indent$}📄 {} ({})", "", name, &file.size, indent = depth);
+                debug!("{:indent$}📄 {} ({})", "", name, file.size, indent = depth);
             }
             Node::Directory(dir) => {
-                println!("{:indent$}📂 {}", "", name, indent = depth);
+                debug!("{:indent$}📂 {}", "", name, indent = depth);
                 print_tree(dir, depth + 2);
             }
         }
@@ -236,4 +268,26 @@ $ ls
             .unwrap();
         assert_eq!(solution, 24933642);
     }
+
+    #[test]
+    fn oversized_file() {
+        let input = INPUT.replace("14848514 b.txt", "99999999999999999999 b.txt");
+        assert!(parse(input.lines()).is_err());
+        let input = INPUT.replace("14848514 b.txt", "70000001 b.txt");
+        let error = parse(input.lines()).unwrap_err();
+        assert_eq!(error.line(), Some(4));
+        assert_eq!(error.span(), Some(0..8));
+    }
+
+    #[test]
+    fn generated() {
+        let input = generator::generate(50, &mut Rng::new(1));
+        // The transcript climbs back to the root, unlike real ones
+        let root = parse(input.lines()).unwrap();
+        let (size, _) = solve_p1(&root);
+        assert!((41_000_000..=70_000_000).contains(&size));
+        assert!(solve_p2(&root, get_missing_space(&root).unwrap())
+            .1
+            .is_some());
+    }
 }
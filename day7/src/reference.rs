@@ -0,0 +1,72 @@
+//! Naive solutions, checked against the real ones on generated inputs.
+
+use std::collections::HashMap;
+
+use common::property::assert_matches_reference;
+
+use crate::{Day7, MAX_USAGE};
+
+/// Size of every directory, as the sum of the files whose path starts with its own.
+fn directory_sizes(input: &str) -> Option<Vec<usize>> {
+    let mut cwd: Vec<&str> = Vec::new();
+    let mut directories = vec![Vec::new()];
+    let mut files = HashMap::new();
+
+    for line in input.lines() {
+        let words: Vec<&str> = line.split(' ').collect();
+        match words[..] {
+            ["$", "cd", "/"] => cwd.clear(),
+            ["$", "cd", ".."] => {
+                cwd.pop()?;
+            }
+            ["$", "cd", name] => {
+                cwd.push(name);
+                directories.push(cwd.clone());
+            }
+            ["$", "ls"] => {}
+            ["dir", _] => {}
+            [size, name] => {
+                let mut path = cwd.clone();
+                path.push(name);
+                files.insert(path, size.parse::<usize>().ok()?);
+            }
+            _ => return None,
+        }
+    }
+
+    let sizes = directories
+        .iter()
+        .map(|directory| {
+            files
+                .iter()
+                .filter(|(path, _)| path.starts_with(directory))
+                .map(|(_, size)| size)
+                .sum()
+        })
+        .collect();
+    Some(sizes)
+}
+
+fn part1(input: &str) -> Option<usize> {
+    let sizes = directory_sizes(input)?;
+    Some(sizes.iter().filter(|&&size| size <= 100_000).sum())
+}
+
+/// The root comes first, and the smallest directory freeing enough space wins.
+fn part2(input: &str) -> Option<usize> {
+    let sizes = directory_sizes(input)?;
+    let missing = sizes[0]
+        .checked_sub(MAX_USAGE)
+        .filter(|&missing| missing > 0)?;
+    sizes.into_iter().filter(|&size| size >= missing).min()
+}
+
+#[test]
+fn part1_matches_reference() {
+    assert_matches_reference::<Day7, _>(1, part1);
+}
+
+#[test]
+fn part2_matches_reference() {
+    assert_matches_reference::<Day7, _>(2, part2);
+}
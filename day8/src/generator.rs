@@ -0,0 +1,12 @@
+use common::rng::Rng;
+
+/// Square forest of `size` trees per side, at least 3 so that some tree isn't on the edge.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(3);
+    let mut input = String::new();
+    for _ in 0..size {
+        input.extend((0..size).map(|_| (b'0' + rng.below(10) as u8) as char));
+        input.push('\n');
+    }
+    input
+}
@@ -1,68 +1,45 @@
 use std::borrow::Borrow;
 
-use common::lines_from_file;
-use common::pair::Pair;
+use common::{debug, rng::Rng, Error, Grid, Solution};
 
+mod generator;
 mod part1;
 mod part2;
+#[cfg(test)]
+mod reference;
 
-type Error = &'static str;
-
-pub fn run(filename: &str) -> Result<(), Error> {
-    let forest = Forest::parse(lines_from_file(filename)?)?;
-    println!("Size: {}", &forest.size);
-    let solution_p1 = part1::solve(&forest);
-    println!("Solution P1: {solution_p1}");
-    let solution_p2 = part2::solve(&forest);
-    println!("Solution P2: {solution_p2}");
-    Ok(())
-}
+pub struct Day8;
 
-#[derive(Debug)]
-pub struct Forest<T> {
-    size: Pair,
-    buffer: Vec<T>,
-}
+impl Solution for Day8 {
+    const DAY: u8 = 8;
 
-impl Forest<u8> {
-    fn parse(mut input: impl Iterator<Item = impl Borrow<str>>) -> Result<Forest<u8>, Error> {
-        let first = input.next().ok_or("Empty")?;
-        let mut height = 1;
-        let width = first.borrow().len() as i32;
+    type Input = Grid<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-        let mut buffer = Vec::with_capacity((width * width) as usize);
-        buffer.extend(parse_line(first)?);
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse(input.lines())
+    }
 
-        for line in input {
-            buffer.extend(parse_line(line)?);
-            height += 1;
-        }
+    fn part1(forest: &Self::Input) -> Result<usize, Error> {
+        Ok(part1::solve(forest))
+    }
 
-        Ok(Forest {
-            buffer,
-            size: Pair {
-                x: width,
-                y: height,
-            },
-        })
+    fn part2(forest: &Self::Input) -> Result<usize, Error> {
+        part2::solve(forest).ok_or_else(|| "Every tree is on the edge".into())
     }
-}
 
-impl<T: Copy> Forest<T> {
-    fn get(&self, Pair { x, y }: Pair) -> T {
-        self.buffer[(y * self.size.x + x) as usize]
+    fn describe(forest: &Self::Input) {
+        debug!("Size: {}", forest.size());
     }
-    fn set(&mut self, Pair { x, y }: Pair, value: T) {
-        self.buffer[(y * self.size.x + x) as usize] = value;
+
+    fn generate(size: usize, rng: &mut Rng) -> Result<String, Error> {
+        Ok(generator::generate(size, rng))
     }
 }
 
-fn parse_line(line: impl Borrow<str>) -> Result<Vec<u8>, Error> {
-    line.borrow()
-        .chars()
-        .map(|c| Some(c.to_digit(10)? as u8))
-        .collect::<Option<Vec<_>>>()
-        .ok_or("Couldn't parse input")
+fn parse(input: impl Iterator<Item = impl Borrow<str>>) -> Result<Grid<u8>, Error> {
+    Grid::parse(input, |c| Some(c.to_digit(10)? as u8))
 }
 
 #[cfg(test)]
@@ -1,36 +1,36 @@
-use crate::Forest;
-use common::pair::{CoordsIter, Direction, Pair, DOWN, LEFT, RIGHT, UP};
-
-pub fn solve(forest: &Forest<u8>) -> usize {
-    (1..(forest.size.x - 1))
-        .flat_map(|x| (1..(forest.size.y - 1)).map(move |y| Pair { x, y }))
+use common::{
+    pair::{CoordsIter, Direction, Pair, DIRECTIONS4},
+    rect::Rect,
+    Grid,
+};
+
+/// Best scenic score, `None` when every tree is on the edge.
+pub fn solve(forest: &Grid<u8>) -> Option<usize> {
+    let inner = Rect::new(Pair { x: 1, y: 1 }, forest.size() - Pair { x: 2, y: 2 });
+    inner
+        .row_major()
         .map(|pair| get_scenic_score(forest, pair))
         .max()
-        .expect("Forest should not be empty")
 }
 
-fn get_scenic_score(forest: &Forest<u8>, coords: Pair) -> usize {
-    [UP, RIGHT, DOWN, LEFT]
+fn get_scenic_score(forest: &Grid<u8>, coords: Pair) -> usize {
+    DIRECTIONS4
         .into_iter()
         .map(|direction| get_viewing_distance(forest, coords, direction))
         .reduce(|l, r| l * r)
         .expect("Scores are from 4 direction")
 }
 
-fn get_viewing_distance(forest: &Forest<u8>, coords: Pair, direction: Direction) -> usize {
-    let height = forest.get(coords);
+fn get_viewing_distance(forest: &Grid<u8>, coords: Pair, direction: Direction) -> usize {
+    let height = forest[coords];
 
-    let positions = CoordsIter {
-        size: forest.size,
-        current: Some(coords),
-        direction,
-    };
+    let positions = CoordsIter::new(forest.size(), coords, direction);
 
     let mut count = 0;
 
     for position in positions {
         count += 1;
-        if forest.get(position) >= height {
+        if forest[position] >= height {
             break;
         }
     }
@@ -41,12 +41,18 @@ fn get_viewing_distance(forest: &Forest<u8>, coords: Pair, direction: Direction)
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::tests::INPUT;
+    use crate::{parse, tests::INPUT};
 
     #[test]
     fn solution() {
-        let forest = Forest::parse(INPUT.lines()).unwrap();
+        let forest = parse(INPUT.lines()).unwrap();
         let solution = solve(&forest);
-        assert_eq!(solution, 8);
+        assert_eq!(solution, Some(8));
+    }
+
+    #[test]
+    fn edges_only() {
+        let forest = parse(["303", "255"].into_iter()).unwrap();
+        assert_eq!(solve(&forest), None);
     }
 }
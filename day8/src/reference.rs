@@ -0,0 +1,70 @@
+//! Naive solutions, checked against the real ones on generated inputs.
+
+use common::property::assert_matches_reference;
+
+use crate::Day8;
+
+fn forest(input: &str) -> Option<Vec<Vec<u32>>> {
+    input
+        .lines()
+        .map(|line| line.chars().map(|c| c.to_digit(10)).collect())
+        .collect()
+}
+
+/// Trees seen from `(x, y)` in each direction, nearest first.
+fn lines_of_sight(forest: &[Vec<u32>], x: usize, y: usize) -> [Vec<u32>; 4] {
+    let row = &forest[y];
+    let column: Vec<u32> = forest.iter().map(|row| row[x]).collect();
+    [
+        row[..x].iter().rev().copied().collect(),
+        row[x + 1..].to_vec(),
+        column[..y].iter().rev().copied().collect(),
+        column[y + 1..].to_vec(),
+    ]
+}
+
+/// Looks from every tree towards each edge.
+fn part1(input: &str) -> Option<usize> {
+    let forest = forest(input)?;
+    let mut visible = 0;
+    for (y, row) in forest.iter().enumerate() {
+        for (x, &height) in row.iter().enumerate() {
+            if lines_of_sight(&forest, x, y)
+                .iter()
+                .any(|line| line.iter().all(|&tree| tree < height))
+            {
+                visible += 1;
+            }
+        }
+    }
+    Some(visible)
+}
+
+/// Scores every tree, those on the edge scoring 0.
+fn part2(input: &str) -> Option<usize> {
+    let forest = forest(input)?;
+    let mut best = None;
+    for (y, row) in forest.iter().enumerate() {
+        for (x, &height) in row.iter().enumerate() {
+            let score: usize = lines_of_sight(&forest, x, y)
+                .iter()
+                .map(|line| match line.iter().position(|&tree| tree >= height) {
+                    Some(blocking) => blocking + 1,
+                    None => line.len(),
+                })
+                .product();
+            best = best.max(Some(score));
+        }
+    }
+    best
+}
+
+#[test]
+fn part1_matches_reference() {
+    assert_matches_reference::<Day8, _>(1, part1);
+}
+
+#[test]
+fn part2_matches_reference() {
+    assert_matches_reference::<Day8, _>(2, part2);
+}
@@ -0,0 +1,13 @@
+use std::fmt::Write;
+
+use common::rng::Rng;
+
+/// `size` moves of the head.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let direction = rng.choose(&['U', 'D', 'L', 'R']);
+        writeln!(input, "{direction} {}", rng.range(1..=20)).unwrap();
+    }
+    input
+}
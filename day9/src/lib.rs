@@ -1,70 +1,134 @@
 use common::{
-    lines_from_file,
+    error::Context,
+    frames::{Frame, Recorder},
     pair::{Pair, DOWN, LEFT, RIGHT, UP},
+    rect::Rect,
+    render::{gradient, Image, Rgb},
+    rng::Rng,
+    scan, Error, Grid, Solution, SparseGrid,
 };
-use std::{borrow::Borrow, collections::HashSet};
+use std::borrow::Borrow;
 
-type Error = &'static str;
+mod generator;
+#[cfg(test)]
+mod reference;
 
-pub fn run(filename: &str) -> Result<(), Error> {
-    let steps = parser(lines_from_file(filename)?).collect::<Result<Vec<_>, _>>()?;
-    let solution_p1 = solve_p1(steps.iter());
-    println!("P1 Solution: {}", solution_p1);
-    let solution_p2 = solve_p2(steps.iter());
-    println!("P2 Solution: {}", solution_p2);
-    Ok(())
-}
+pub struct Day9;
 
-fn solve_p1(steps: impl Iterator<Item = impl Borrow<Step>>) -> usize {
-    let mut head = Pair { x: 0, y: 0 };
-    let mut tail = Pair { x: 0, y: 0 };
-    let mut tail_positions = HashSet::new();
-    for step in steps {
-        let step = step.borrow();
-        for _ in 0..step.repeat {
-            head = head + step.direction;
-            follow(head, &mut tail);
-            tail_positions.insert(tail);
-        }
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Step>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parser(input.lines()).collect()
     }
 
-    tail_positions.len()
-}
+    fn part1(steps: &Self::Input) -> Result<usize, Error> {
+        Ok(solve_p1(steps.iter()))
+    }
 
-fn follow(head: Pair, tail: &mut Pair) {
-    let diff = *tail - head;
-    if diff.y == 0 && diff.x.abs() > 1 {
-        tail.x -= diff.x.signum();
+    fn part2(steps: &Self::Input) -> Result<usize, Error> {
+        Ok(solve_p2(steps.iter()))
     }
-    if diff.x == 0 && diff.y.abs() > 1 {
-        tail.y -= diff.y.signum();
+
+    fn record(steps: &Self::Input, part: u8, recorder: &mut Recorder) -> Result<(), Error> {
+        let knots = match part {
+            1 => 2,
+            2 => SIZE,
+            _ => return Err(format!("Day {} has no part {part}", Self::DAY).into()),
+        };
+        record(steps, knots, recorder);
+        Ok(())
     }
-    if diff.x.abs() + diff.y.abs() > 2 {
-        tail.x -= diff.x.signum();
-        tail.y -= diff.y.signum();
+
+    fn generate(size: usize, rng: &mut Rng) -> Result<String, Error> {
+        Ok(generator::generate(size, rng))
     }
 }
 
+fn solve_p1(steps: impl Iterator<Item = impl Borrow<Step>>) -> usize {
+    simulate(steps, 2, |_, _| {})
+}
+
 const SIZE: usize = 10;
 
 fn solve_p2(steps: impl Iterator<Item = impl Borrow<Step>>) -> usize {
-    let mut rope: Vec<Pair> = vec![Pair { x: 0, y: 0 }; SIZE];
-    let mut tail_positions = HashSet::new();
+    simulate(steps, SIZE, |_, _| {})
+}
+
+/// Moves a rope of `knots` along `steps`, calling `on_move` with the knots and how many times
+/// the tail visited each position so far after every move, and returns how many positions the
+/// tail visited.
+fn simulate(
+    steps: impl Iterator<Item = impl Borrow<Step>>,
+    knots: usize,
+    mut on_move: impl FnMut(&[Pair], &SparseGrid<u32>),
+) -> usize {
+    let mut rope: Vec<Pair> = vec![Pair::ZERO; knots];
+    let mut tail_positions = SparseGrid::new();
     for step in steps {
         let step = step.borrow();
         for _ in 0..step.repeat {
-            rope[0] = rope[0] + step.direction;
-            for i in 0..SIZE - 1 {
+            rope[0] += step.direction;
+            for i in 0..knots - 1 {
                 follow(rope[i], &mut rope[i + 1]);
             }
-            tail_positions.insert(rope.last().copied().unwrap());
+            *tail_positions.get_or_insert_with(rope[knots - 1], || 0) += 1;
+            on_move(&rope, &tail_positions);
         }
     }
     tail_positions.len()
 }
 
+fn follow(head: Pair, tail: &mut Pair) {
+    let diff = head - *tail;
+    if diff.chebyshev() > 1 {
+        *tail += diff.signum();
+    }
+}
+
+fn record(steps: &[Step], knots: usize, recorder: &mut Recorder) {
+    // Every frame covers the whole area the rope goes through
+    let mut area = SparseGrid::from_iter([(Pair::ZERO, ())]);
+    simulate(steps.iter(), knots, |rope, _| {
+        area.extend(rope.iter().map(|&knot| (knot, ())))
+    });
+    let bounds = area.bounds().expect("The start is part of the area");
+
+    let mut moves = 0;
+    simulate(steps.iter(), knots, |rope, visited| {
+        moves += 1;
+        recorder.push(|| {
+            let caption = format!("Move {moves}, {} positions visited", visited.len());
+            Frame::new(draw_rope(bounds, rope, visited), caption)
+        });
+    });
+}
+
+/// The head in red and other knots in white, over the trail of the tail, brighter where it went
+/// more often.
+fn draw_rope(bounds: Rect, rope: &[Pair], visited: &SparseGrid<u32>) -> Image {
+    let trail = gradient(Rgb(40, 50, 80), Rgb(110, 140, 210), 1, 8);
+    Grid::from_fn(bounds.size, |position| {
+        let position = bounds.min + position;
+        if position == rope[0] {
+            Rgb(230, 60, 50)
+        } else if rope.contains(&position) {
+            Rgb::WHITE
+        } else if let Some(visits) = visited.get(position) {
+            trail(visits)
+        } else {
+            Rgb::BLACK
+        }
+    })
+    .into()
+}
+
 #[derive(Debug)]
-struct Step {
+pub struct Step {
     direction: Pair,
     repeat: i16,
 }
@@ -72,32 +136,32 @@ struct Step {
 fn parser(
     input: impl Iterator<Item = impl Borrow<str>>,
 ) -> impl Iterator<Item = Result<Step, Error>> {
-    input.map(|line| {
-        let mut line = line.borrow().split_whitespace();
-
-        let direction = line
-            .next()
-            .and_then(|c| c.chars().nth(0))
-            .ok_or("Couldn't find direction in line")?;
-
-        let direction = match direction {
-            'U' => Ok(UP),
-            'L' => Ok(LEFT),
-            'R' => Ok(RIGHT),
-            'D' => Ok(DOWN),
-            _ => Err("Unrecognized letter"),
-        }?;
-
-        let repeat = line
-            .next()
-            .ok_or("Number of steps not found in line")?
-            .parse()
-            .or(Err("Couldn't parse number of steps"))?;
-
-        Ok(Step { direction, repeat })
+    input.enumerate().map(|(index, line)| {
+        let line = line.borrow();
+        parse_step(line).at_line(index + 1, line)
     })
 }
 
+fn parse_step(text: &str) -> Result<Step, Error> {
+    let (letter, repeat) = scan!("{} {}", text => char, i16)?;
+
+    let direction = match letter {
+        'U' => UP,
+        'L' => LEFT,
+        'R' => RIGHT,
+        'D' => DOWN,
+        _ => {
+            return Err(Error::at(
+                "Unrecognized letter",
+                text,
+                &text[..letter.len_utf8()],
+            ))
+        }
+    };
+
+    Ok(Step { direction, repeat })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -142,4 +206,16 @@ U 20";
         let solution = solve_p2(steps);
         assert_eq!(solution, 36);
     }
+
+    #[test]
+    fn recording() {
+        let steps: Vec<_> = parser(INPUT.lines()).map(Result::unwrap).collect();
+        let mut recorder = Recorder::new(1);
+        record(&steps, 2, &mut recorder);
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 24);
+        assert!(frames.iter().all(|f| f.image.size() == Pair { x: 6, y: 5 }));
+        assert_eq!(frames[23].caption, "Move 24, 13 positions visited");
+        assert!(Day9::record(&steps, 0, &mut recorder).is_err());
+    }
 }
@@ -0,0 +1,55 @@
+//! Naive solutions, checked against the real ones on generated inputs.
+
+use std::collections::HashSet;
+
+use common::property::assert_matches_reference;
+
+use crate::Day9;
+
+/// Moves the head one square at a time, each knot catching up with the previous one.
+fn tail_positions(input: &str, knots: usize) -> Option<usize> {
+    let mut rope = vec![(0i32, 0i32); knots];
+    let mut visited = HashSet::from([(0, 0)]);
+    for line in input.lines() {
+        let (direction, steps) = line.split_once(' ')?;
+        let (dx, dy) = match direction {
+            "U" => (0, -1),
+            "D" => (0, 1),
+            "L" => (-1, 0),
+            "R" => (1, 0),
+            _ => return None,
+        };
+        for _ in 0..steps.parse::<u32>().ok()? {
+            rope[0].0 += dx;
+            rope[0].1 += dy;
+            for knot in 1..knots {
+                let (x, y) = rope[knot - 1];
+                let (tail_x, tail_y) = &mut rope[knot];
+                if (x - *tail_x).abs() > 1 || (y - *tail_y).abs() > 1 {
+                    *tail_x += (x - *tail_x).signum();
+                    *tail_y += (y - *tail_y).signum();
+                }
+            }
+            visited.insert(rope[knots - 1]);
+        }
+    }
+    Some(visited.len())
+}
+
+fn part1(input: &str) -> Option<usize> {
+    tail_positions(input, 2)
+}
+
+fn part2(input: &str) -> Option<usize> {
+    tail_positions(input, 10)
+}
+
+#[test]
+fn part1_matches_reference() {
+    assert_matches_reference::<Day9, _>(1, part1);
+}
+
+#[test]
+fn part2_matches_reference() {
+    assert_matches_reference::<Day9, _>(2, part2);
+}
@@ -0,0 +1,12 @@
+//! Shared body of the fuzz targets, one per day.
+
+use common::DynSolution;
+
+/// Parses `input` then solves both parts, ignoring their errors: only panics, overflows and
+/// hangs are bugs here.
+pub fn solve(solution: &dyn DynSolution, input: &str) {
+    if let Ok(input) = solution.parse(input) {
+        let _ = solution.part1(input.as_ref());
+        let _ = solution.part2(input.as_ref());
+    }
+}